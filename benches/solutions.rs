@@ -0,0 +1,50 @@
+//! Benches the phases of every day of the selected year with an input in `data/<year>/inputs` using criterion, grouped by day.
+//! Run with `cargo bench --features registry --bench solutions`, see the readme for comparing baselines and other years.
+use std::panic::{self, AssertUnwindSafe};
+
+use advent_of_code::template::{InputSource, Year};
+use criterion::Criterion;
+
+/// Every day's solution, see `build.rs`.
+mod solutions {
+    use advent_of_code::template::registry::Registered;
+
+    include!(concat!(env!("OUT_DIR"), "/solutions.rs"));
+}
+
+fn main() {
+    let mut criterion = Criterion::default().configure_from_args();
+    let year = Year::selected();
+
+    for solution in solutions::SOLUTIONS.iter().filter(|s| s.year == year) {
+        let day = solution.day;
+        let source = InputSource::Puzzle;
+
+        let input = match source.read(day) {
+            Ok(input) if !input.trim().is_empty() => input,
+            _ => {
+                eprintln!("Skipping day {day}, {} is missing.", source.describe(day));
+                continue;
+            }
+        };
+
+        let mut group = criterion.benchmark_group(format!("{year}_day_{day}"));
+
+        (solution.bench_phases)(&mut |name, prepare| {
+            let routine = prepare(&input);
+
+            // parts that aren't implemented yet or panic on the input aren't benched.
+            match panic::catch_unwind(AssertUnwindSafe(&routine)) {
+                Ok(true) => {
+                    group.bench_function(name, |b| b.iter(&routine));
+                }
+                Ok(false) => eprintln!("Skipping day {day} {name}, it has no answer."),
+                Err(_) => eprintln!("Skipping day {day} {name}, it panicked."),
+            }
+        });
+
+        group.finish();
+    }
+
+    criterion.final_summary();
+}
@@ -0,0 +1,67 @@
+//! Generates the registry of solutions that the `registry` feature compiles into the main binary and the benches.
+//! Every day like `src/bin/2023-05.rs` is included as a module and registered with its `run_parts` and `bench_phases` functions.
+//! Days without a year like `src/bin/05.rs` belong to `AOC_YEAR`, unless that year has the same day with a year.
+use std::fmt::Write;
+use std::path::Path;
+use std::{env, fs};
+
+fn main() {
+    // NOTE: without the feature, editing a day must not rerun this script, which would rebuild the library.
+    if env::var_os("CARGO_FEATURE_REGISTRY").is_none() {
+        println!("cargo:rerun-if-changed=build.rs");
+        return;
+    }
+    println!("cargo:rerun-if-changed=src/bin");
+    println!("cargo:rerun-if-env-changed=AOC_YEAR");
+
+    let bin_dir = Path::new(&env::var("CARGO_MANIFEST_DIR").unwrap()).join("src/bin");
+    let configured_year: Option<u16> = env::var("AOC_YEAR").ok().and_then(|y| y.parse().ok());
+
+    // (year, day, whether the file name has no year, file name)
+    let mut days: Vec<(u16, u8, bool, String)> = fs::read_dir(&bin_dir)
+        .unwrap()
+        .filter_map(|entry| {
+            let name = entry.ok()?.file_name().into_string().ok()?;
+            let stem = name.strip_suffix(".rs")?;
+            // only days are registered, e.g. `2023-05.rs` or `05.rs`.
+            let (year, day, legacy) = match stem.split_once('-') {
+                Some((year, day)) if year.len() == 4 => (year.parse().ok()?, day, false),
+                None => (configured_year?, stem, true),
+                _ => return None,
+            };
+            if day.len() != 2 {
+                return None;
+            }
+            Some((year, day.parse().ok()?, legacy, name))
+        })
+        .collect();
+    days.sort_unstable();
+    // NOTE: a day with a year sorts before the same day without one. Commands only use the latter while the former doesn't exist.
+    days.dedup_by_key(|(year, day, _, _)| (*year, *day));
+
+    let mut code = String::new();
+
+    // NOTE: the days are linted in their own binaries. Here, `main` is unused and so are the imports
+    // of their tests when the benches are checked with `cfg(test)`.
+    for (year, day, _, name) in &days {
+        let path = bin_dir.join(name);
+        writeln!(
+            code,
+            "#[allow(dead_code, unused_imports)]\n#[path = {path:?}]\nmod day_{year}_{day:02};"
+        )
+        .unwrap();
+    }
+
+    writeln!(code, "\npub static SOLUTIONS: &[Registered] = &[").unwrap();
+    for (year, day, _, _) in &days {
+        writeln!(
+            code,
+            "    Registered {{ year: advent_of_code::year!({year}), day: advent_of_code::day!({day}), run_parts: day_{year}_{day:02}::run_parts, bench_phases: day_{year}_{day:02}::bench_phases }},"
+        )
+        .unwrap();
+    }
+    writeln!(code, "];").unwrap();
+
+    let out = Path::new(&env::var("OUT_DIR").unwrap()).join("solutions.rs");
+    fs::write(out, code).unwrap();
+}
@@ -1,6 +1,12 @@
 use std::collections::HashMap;
 
-advent_of_code::solution!(1);
+use advent_of_code::utils::Counter;
+
+advent_of_code::solution!(
+    1,
+    variants: [part_two_sorted],
+    examples: [(1, part_one, 11), (1, part_two, 31), (1, part_two_sorted, 31)]
+);
 
 pub fn part_one(input: &str) -> Option<u32> {
     let (mut left, mut right): (Vec<_>, Vec<_>) = input
@@ -27,6 +33,23 @@ pub fn part_one(input: &str) -> Option<u32> {
 }
 
 pub fn part_two(input: &str) -> Option<u32> {
+    let (left, right): (Vec<_>, Counter<_>) = input
+        .lines()
+        .map(|line| {
+            let (a, b) = line.split_once(' ').unwrap();
+            (
+                a.trim().parse::<u32>().unwrap(),
+                b.trim().parse::<u32>().unwrap(),
+            )
+        })
+        .unzip();
+
+    let res = left.iter().map(|n| *n * right.get(n) as u32).sum();
+
+    Some(res)
+}
+
+pub fn part_two_sorted(input: &str) -> Option<u32> {
     let (left, mut right): (Vec<_>, Vec<_>) = input
         .lines()
         .map(|line| {
@@ -59,20 +82,3 @@ pub fn part_two(input: &str) -> Option<u32> {
 
     Some(res)
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(11));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(31));
-    }
-}
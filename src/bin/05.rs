@@ -1,9 +1,10 @@
+use advent_of_code::template::Solution;
 use itertools::Itertools;
 use std::collections::HashMap;
 
-advent_of_code::solution!(5);
+advent_of_code::solution!(5, Day05);
 
-type Rules = HashMap<u32, Vec<u32>>;
+pub type Rules = HashMap<u32, Vec<u32>>;
 
 fn parse_rules(rules: &str) -> Rules {
     let mut r: Rules = HashMap::new();
@@ -55,67 +56,80 @@ fn sort_updates(updates: &mut [u32], rules: &Rules) {
     }
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
-    let (rs, us) = input.split_once("\n\n").unwrap();
-    let rules = parse_rules(rs);
-
-    let mut result = 0;
-    for update_line in us.trim().lines() {
-        let updates = update_line
-            .split(',')
-            .map(|n| atoi::atoi::<u32>(n.as_bytes()).unwrap())
+pub struct Day05;
+
+impl Solution for Day05 {
+    /// The ordering rules and the list of updates.
+    type Input = (Rules, Vec<Vec<u32>>);
+    type Output1 = u32;
+    type Output2 = u32;
+
+    fn parse(input: &str) -> Self::Input {
+        let (rs, us) = input.split_once("\n\n").unwrap();
+        let rules = parse_rules(rs);
+
+        let updates = us
+            .trim()
+            .lines()
+            .map(|update_line| {
+                let updates = update_line
+                    .split(',')
+                    .map(|n| atoi::atoi::<u32>(n.as_bytes()).unwrap())
+                    .collect_vec();
+                assert!(updates.len() & 1 == 1);
+                updates
+            })
             .collect_vec();
-        let midid = updates.len() / 2;
-        assert!(updates.len() & 1 == 1);
-
-        if updates.iter().enumerate().skip(1).all(|(i, n)| {
-            let Some(after_rule) = rules.get(n) else {
-                // No rule for this N - no need to check
-                return true;
-            };
-
-            let slice_to_i = &updates[..i];
 
-            // if slice contains any of the values return false
-            !after_rule.iter().any(|n| slice_to_i.contains(n))
-        }) {
-            result += updates[midid];
-        }
+        (rules, updates)
     }
 
-    Some(result)
-}
-
-pub fn part_two(input: &str) -> Option<u32> {
-    let (rs, us) = input.split_once("\n\n").unwrap();
-    let rules = parse_rules(rs);
+    fn part_one((rules, updates): &Self::Input) -> Option<u32> {
+        let mut result = 0;
+        for updates in updates {
+            let midid = updates.len() / 2;
 
-    let mut result = 0;
-    for update_line in us.trim().lines() {
-        let mut updates = update_line
-            .split(',')
-            .map(|n| atoi::atoi::<u32>(n.as_bytes()).unwrap())
-            .collect_vec();
-        assert!(updates.len() & 1 == 1);
+            if updates.iter().enumerate().skip(1).all(|(i, n)| {
+                let Some(after_rule) = rules.get(n) else {
+                    // No rule for this N - no need to check
+                    return true;
+                };
 
-        // Find invalid
-        if updates.iter().enumerate().skip(1).any(|(i, n)| {
-            let Some(after_rules) = rules.get(n) else {
-                // No rule for this N - no need to check
-                return false;
-            };
+                let slice_to_i = &updates[..i];
 
-            // if slice contains any of the values return true
-            let slice_to_i = &updates[..i];
-            after_rules.iter().any(|n| slice_to_i.contains(n))
-        }) {
-            sort_updates(&mut updates, &rules);
-            let midid = updates.len() / 2;
-            result += updates[midid];
+                // if slice contains any of the values return false
+                !after_rule.iter().any(|n| slice_to_i.contains(n))
+            }) {
+                result += updates[midid];
+            }
         }
+
+        Some(result)
     }
 
-    Some(result)
+    fn part_two((rules, updates): &Self::Input) -> Option<u32> {
+        let mut result = 0;
+        for updates in updates {
+            // Find invalid
+            if updates.iter().enumerate().skip(1).any(|(i, n)| {
+                let Some(after_rules) = rules.get(n) else {
+                    // No rule for this N - no need to check
+                    return false;
+                };
+
+                // if slice contains any of the values return true
+                let slice_to_i = &updates[..i];
+                after_rules.iter().any(|n| slice_to_i.contains(n))
+            }) {
+                let mut updates = updates.clone();
+                sort_updates(&mut updates, rules);
+                let midid = updates.len() / 2;
+                result += updates[midid];
+            }
+        }
+
+        Some(result)
+    }
 }
 
 #[cfg(test)]
@@ -124,13 +138,15 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let input = Day05::parse(&advent_of_code::template::read_file("examples", DAY));
+        let result = Day05::part_one(&input);
         assert_eq!(result, Some(143));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let input = Day05::parse(&advent_of_code::template::read_file("examples", DAY));
+        let result = Day05::part_two(&input);
         assert_eq!(result, Some(123));
     }
 }
@@ -1,5 +1,5 @@
+use advent_of_code::template::Ctx;
 use indexmap::IndexSet;
-use indicatif::ProgressIterator;
 use itertools::Itertools;
 use std::collections::HashSet;
 
@@ -98,11 +98,10 @@ pub fn part_one(input: &str) -> Option<u32> {
 
 type LocationsAndDirs = IndexSet<((i32, i32), (i32, i32))>;
 
-fn find_looping_paths(locs_and_dirs: &LocationsAndDirs, map: &Map) -> usize {
+fn find_looping_paths(locs_and_dirs: &LocationsAndDirs, map: &Map, ctx: &Ctx) -> usize {
     let mut tested_locs = HashSet::new();
-    let result = locs_and_dirs
-        .iter()
-        .progress()
+    let result = ctx
+        .progress(locs_and_dirs.iter())
         .filter(|(start_loc, start_dir)| {
             let mut encountered_locs_and_dirs = HashSet::new();
             let addpos = step(start_loc, start_dir);
@@ -165,7 +164,7 @@ fn find_looping_paths(locs_and_dirs: &LocationsAndDirs, map: &Map) -> usize {
     result
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
+pub fn part_two(input: &str, ctx: &Ctx) -> Option<u32> {
     let (map, mut guard_loc) = map_and_guard_loc(input);
 
     let mut locations_with_dirs = IndexSet::new();
@@ -194,7 +193,7 @@ pub fn part_two(input: &str) -> Option<u32> {
         }
     }
 
-    let result = find_looping_paths(&locations_with_dirs, &map);
+    let result = find_looping_paths(&locations_with_dirs, &map, ctx);
 
     Some(result as u32)
 }
@@ -211,7 +210,10 @@ mod tests {
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(
+            &advent_of_code::template::read_file("examples", DAY),
+            &Ctx::default(),
+        );
         assert_eq!(result, Some(6));
     }
 }
@@ -1,26 +1,36 @@
+use advent_of_code::template::Solution;
+use advent_of_code::utils::math::concat_digits;
 use itertools::Itertools;
 use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
 
-advent_of_code::solution!(7);
+advent_of_code::solution!(7, Day07, examples: [(1, part_one, 3749), (1, part_two, 11387)]);
 
-pub fn part_one(input: &str) -> Option<u64> {
-    let equations = input.lines().map(Equation::from).collect_vec();
+pub struct Day07;
 
-    let result: u64 = equations
-        .par_iter()
-        .filter_map(Equation::try_solve_p1)
-        .sum();
-    Some(result)
-}
+impl Solution for Day07 {
+    type Input = Vec<Equation>;
+    type Output1 = u64;
+    type Output2 = u64;
+
+    fn parse(input: &str) -> Self::Input {
+        input.lines().map(Equation::from).collect_vec()
+    }
 
-pub fn part_two(input: &str) -> Option<u64> {
-    let equations = input.lines().map(Equation::from).collect_vec();
+    fn part_one(equations: &Self::Input) -> Option<u64> {
+        let result: u64 = equations
+            .par_iter()
+            .filter_map(Equation::try_solve_p1)
+            .sum();
+        Some(result)
+    }
 
-    let result: u64 = equations
-        .par_iter()
-        .filter_map(Equation::try_solve_p2)
-        .sum();
-    Some(result)
+    fn part_two(equations: &Self::Input) -> Option<u64> {
+        let result: u64 = equations
+            .par_iter()
+            .filter_map(Equation::try_solve_p2)
+            .sum();
+        Some(result)
+    }
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -31,7 +41,7 @@ enum Op {
 }
 
 #[derive(Debug)]
-struct Equation {
+pub struct Equation {
     solution: u64,
     numbers: Vec<u64>,
 }
@@ -43,19 +53,18 @@ impl Equation {
             .map(|_| [Op::Plus, Op::Multi].into_iter())
             .multi_cartesian_product();
 
-        let is_valid =
-            permutations.any(|p| {
-                let r = self.numbers.iter().skip(1).enumerate().fold(
-                    self.numbers[0],
-                    |acc, (i, &val)| match p[i] {
-                        Op::Plus => acc + val,
-                        Op::Multi => acc * val,
-                        Op::Concat => unreachable!(),
-                    },
-                );
+        let is_valid = permutations.any(|p| {
+            let r = self.numbers.iter().skip(1).enumerate().try_fold(
+                self.numbers[0],
+                |acc, (i, &val)| match p[i] {
+                    Op::Plus => acc.checked_add(val),
+                    Op::Multi => acc.checked_mul(val),
+                    Op::Concat => unreachable!(),
+                },
+            );
 
-                r == self.solution
-            });
+            r == Some(self.solution)
+        });
 
         if is_valid {
             Some(self.solution)
@@ -70,19 +79,18 @@ impl Equation {
             .map(|_| [Op::Plus, Op::Multi, Op::Concat].into_iter())
             .multi_cartesian_product();
 
-        let is_valid =
-            permutations.any(|p| {
-                let r = self.numbers.iter().skip(1).enumerate().fold(
-                    self.numbers[0],
-                    |acc, (i, &val)| match p[i] {
-                        Op::Plus => acc + val,
-                        Op::Multi => acc * val,
-                        Op::Concat => acc * 10u64.pow(val.ilog10() + 1) + val,
-                    },
-                );
+        let is_valid = permutations.any(|p| {
+            let r = self.numbers.iter().skip(1).enumerate().try_fold(
+                self.numbers[0],
+                |acc, (i, &val)| match p[i] {
+                    Op::Plus => acc.checked_add(val),
+                    Op::Multi => acc.checked_mul(val),
+                    Op::Concat => concat_digits(acc, val),
+                },
+            );
 
-                r == self.solution
-            });
+            r == Some(self.solution)
+        });
 
         if is_valid {
             Some(self.solution)
@@ -112,14 +120,9 @@ mod tests {
     use super::*;
 
     #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(3749));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(11387));
+    fn test_overflowing_equation() {
+        let input = Day07::parse("1: 18446744073709551615 2");
+        assert_eq!(Day07::part_one(&input), Some(0));
+        assert_eq!(Day07::part_two(&input), Some(0));
     }
 }
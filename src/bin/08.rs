@@ -1,4 +1,4 @@
-advent_of_code::solution!(8);
+advent_of_code::solution!(8, examples: [(1, part_one, 14), (1, part_two, 34)]);
 
 use std::collections::HashSet;
 
@@ -57,20 +57,3 @@ pub fn part_two(input: &str) -> Option<u32> {
 
     Some(antinodes.len() as u32)
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(14));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(34));
-    }
-}
@@ -1,9 +1,10 @@
 use std::{
-    collections::HashSet,
     iter,
-    ops::{Deref, DerefMut},
+    ops::{Deref, DerefMut, Range},
 };
 
+use advent_of_code::utils::IntervalSet;
+
 advent_of_code::solution!(9);
 
 pub fn part_one(input: &str) -> Option<usize> {
@@ -42,91 +43,26 @@ pub fn part_one(input: &str) -> Option<usize> {
 }
 
 pub fn part_two(input: &str) -> Option<usize> {
-    let mut repr = generate_memory_representation(input);
-    // last file end range
-    let mut last_file_er = repr.len();
-    let mut checked_file_nums = HashSet::with_capacity(2000);
-
-    'outer: loop {
-        let mut operating_file_num = None;
-        let Some(lf_er) = repr[..last_file_er]
-            .iter()
-            .enumerate()
-            .rev()
-            .find_map(|(i, v)| {
-                let x = (*v)?;
-
-                if checked_file_nums.contains(&x) {
-                    None
-                } else {
-                    operating_file_num = Some(x);
-                    Some(i)
-                }
-            })
-        else {
-            break;
-        };
-        last_file_er = lf_er;
-
-        let last_file_len = repr[..=last_file_er]
-            .iter()
-            .rev()
-            .take_while(|mc| mc.is_some() && mc == &&operating_file_num)
-            .count();
-
-        let last_file_sr = last_file_er - (last_file_len - 1);
+    let (mut files, mut free) = generate_span_representation(input);
 
-        if !checked_file_nums.insert(repr[last_file_sr].unwrap()) {
-            last_file_er = last_file_sr;
+    // Try to move each file exactly once, in order of decreasing file number.
+    for file in files.iter_mut().rev() {
+        let len = file.end - file.start;
+        let Some(fit) = free.first_fit(len) else {
             continue;
-        }
+        };
 
-        let mut find_empty_id = 0;
-        'inner: loop {
-            let Some(mut first_empty_sr) = repr[find_empty_id..]
-                .iter()
-                .enumerate()
-                .find_map(|(i, v)| if v.is_none() { Some(i) } else { None })
-            else {
-                last_file_er = last_file_sr;
-                continue 'outer;
-            };
-
-            first_empty_sr += find_empty_id;
-
-            if first_empty_sr >= last_file_sr {
-                last_file_er = last_file_sr;
-                continue 'outer;
-            }
-
-            let first_empty_len = repr[first_empty_sr..]
-                .iter()
-                .take_while(|mc| mc.is_none())
-                .count();
-
-            let first_empty_er = first_empty_sr + (first_empty_len.saturating_sub(1));
-
-            if last_file_len > first_empty_len {
-                find_empty_id = first_empty_er + 1;
-                continue 'inner;
-            } else {
-                // swap
-                for i in 0..last_file_len {
-                    let e_id = first_empty_sr + i;
-                    let f_id = last_file_sr + i;
-                    repr.swap(e_id, f_id);
-                }
-
-                last_file_er = last_file_sr;
-                continue 'outer;
-            }
+        if fit.start < file.start {
+            free.remove(fit.clone());
+            // NOTE: the freed up space is never used again since all the remaining files are to the left of it.
+            *file = fit;
         }
     }
 
-    let res: usize = repr
+    let res: usize = files
         .iter()
         .enumerate()
-        .filter_map(|(i, o)| o.map(|v| v * i))
+        .map(|(file_num, span)| file_num * span.clone().sum::<usize>())
         .sum();
 
     Some(res)
@@ -179,6 +115,26 @@ fn generate_memory_representation(input: &str) -> Memory {
     Memory(res)
 }
 
+/// Returns the span of each file, indexed by file number, and the set of empty spans.
+fn generate_span_representation(input: &str) -> (Vec<Range<usize>>, IntervalSet<usize>) {
+    let mut files = Vec::with_capacity(input.len() / 2 + 1);
+    let mut free = IntervalSet::new();
+
+    let mut position = 0;
+    for (i, &c) in input.trim().as_bytes().iter().enumerate() {
+        let len = atoi::ascii_to_digit::<usize>(c).expect("unable to convert char to usize");
+        let span = position..position + len;
+        if i & 1 == 0 {
+            files.push(span);
+        } else {
+            free.insert(span);
+        }
+        position += len;
+    }
+
+    (files, free)
+}
+
 #[allow(dead_code)]
 fn debug_string_repr(repr: &[Option<usize>]) -> String {
     let mut s = String::with_capacity(repr.len());
@@ -1,5 +1,7 @@
 use std::collections::HashMap;
 
+use advent_of_code::utils::math::{digit_count, split_digits};
+
 advent_of_code::solution!(11);
 
 pub fn part_one(input: &str) -> Option<usize> {
@@ -53,10 +55,10 @@ fn solve_n_times(input: &str, n: usize) -> Option<usize> {
             let (first_id, second_id) = if num == 0 {
                 (get_index(1), None)
             } else {
-                let num_of_digits = num.ilog10() + 1;
-                let pow = 10u64.pow(num_of_digits / 2);
+                let num_of_digits = digit_count(num);
                 if num_of_digits % 2 == 0 {
-                    (get_index(num / pow), Some(get_index(num % pow)))
+                    let (left, right) = split_digits(num, num_of_digits / 2);
+                    (get_index(left), Some(get_index(right)))
                 } else {
                     (get_index(num * 2024), None)
                 }
@@ -1,14 +1,23 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve, time};
+use advent_of_code::template::commands::{
+    all, confirm, download, migrate, read, scaffold, solve, time,
+};
+use advent_of_code::template::Year;
 use args::{parse, AppArguments};
 
 #[cfg(feature = "today")]
-use advent_of_code::template::Day;
+use advent_of_code::template::{
+    commands::wait,
+    unlock::{self, Clock, SystemClock},
+};
 #[cfg(feature = "today")]
 use std::process;
 
 mod args {
-    use advent_of_code::template::Day;
-    use std::process;
+    use advent_of_code::template::{
+        runner::{parse_duration, parse_threads, Format},
+        BenchOptions, Day, DaySet, InputSource, Year,
+    };
+    use std::{process, time::Duration};
 
     pub enum AppArguments {
         Download {
@@ -23,38 +32,109 @@ mod args {
             overwrite: bool,
         },
         Solve {
-            day: Day,
+            days: DaySet,
             release: bool,
             dhat: bool,
+            mem: bool,
+            quiet: bool,
+            threads: Option<usize>,
             submit: Option<u8>,
+            timeout: Option<Duration>,
+            format: Option<Format>,
+            input: InputSource,
         },
         All {
+            days: Option<DaySet>,
             release: bool,
+            timeout: Option<Duration>,
+            mem: bool,
+            threads: Option<usize>,
+        },
+        Confirm {
+            day: Day,
+            part: Option<u8>,
         },
         Time {
             all: bool,
-            day: Option<Day>,
+            days: Option<DaySet>,
             store: bool,
+            bench: BenchOptions,
+            timeout: Option<Duration>,
+            mem: bool,
+            threads: Option<usize>,
+            scaling: bool,
         },
+        Migrate,
         #[cfg(feature = "today")]
         Today,
+        #[cfg(feature = "today")]
+        Wait,
     }
 
-    pub fn parse() -> Result<AppArguments, Box<dyn std::error::Error>> {
+    /// Parses the command and the year it works on, [`None`] if no `--year` is passed.
+    pub fn parse() -> Result<(AppArguments, Option<Year>), Box<dyn std::error::Error>> {
         let mut args = pico_args::Arguments::from_env();
 
-        let app_args = match args.subcommand()?.as_deref() {
+        let subcommand = args.subcommand()?;
+        // NOTE: parsed first, commands like `solve` expect the day as the first free argument.
+        let year = args.opt_value_from_str("--year")?;
+
+        let app_args = match subcommand.as_deref() {
             Some("all") => AppArguments::All {
                 release: args.contains("--release"),
+                timeout: args.opt_value_from_fn("--timeout", parse_duration)?,
+                mem: args.contains("--mem"),
+                threads: args.opt_value_from_fn("--threads", parse_threads)?,
+                // NOTE: parsed last, the days are the only free argument.
+                days: args.opt_free_from_str()?,
+            },
+            Some("confirm") => AppArguments::Confirm {
+                part: args.opt_value_from_str("--part")?,
+                day: args.free_from_str()?,
             },
             Some("time") => {
                 let all = args.contains("--all");
                 let store = args.contains("--store");
+                let timeout = args.opt_value_from_fn("--timeout", parse_duration)?;
+                let mem = args.contains("--mem");
+                let threads = args.opt_value_from_fn("--threads", parse_threads)?;
+                let scaling = args.contains("--scaling");
+
+                let defaults = BenchOptions::default();
+                let bench = BenchOptions {
+                    time: args
+                        .opt_value_from_fn("--bench-time", parse_duration)?
+                        .unwrap_or(defaults.time),
+                    min_samples: args
+                        .opt_value_from_str("--min-samples")?
+                        .unwrap_or(defaults.min_samples),
+                    max_samples: args
+                        .opt_value_from_str("--max-samples")?
+                        .unwrap_or(defaults.max_samples),
+                    warmup: args.opt_value_from_str("--warmup")?.or(defaults.warmup),
+                    filter_outliers: args.contains("--filter-outliers"),
+                }
+                .validate()?;
+
+                let days = args.opt_free_from_str()?;
+                if scaling && days.is_none() {
+                    return Err("`--scaling` needs a day, e.g. `cargo time 7 --scaling`.".into());
+                }
+                if all && days.is_some() {
+                    return Err(
+                        "`--all` can't be combined with days, e.g. `cargo time 1-5`.".into(),
+                    );
+                }
 
                 AppArguments::Time {
                     all,
-                    day: args.opt_free_from_str()?,
+                    days,
                     store,
+                    bench,
+                    timeout,
+                    mem,
+                    threads,
+                    scaling,
                 }
             }
             Some("download") => AppArguments::Download {
@@ -68,14 +148,64 @@ mod args {
                 download: args.contains("--download"),
                 overwrite: args.contains("--overwrite"),
             },
-            Some("solve") => AppArguments::Solve {
-                day: args.free_from_str()?,
-                release: args.contains("--release"),
-                submit: args.opt_value_from_str("--submit")?,
-                dhat: args.contains("--dhat"),
-            },
+            Some("solve") => {
+                let days = args.free_from_str()?;
+                let release = args.contains("--release");
+                let submit = args.opt_value_from_str("--submit")?;
+                let dhat = args.contains("--dhat");
+                let mem = args.contains("--mem");
+                let quiet = args.contains("--quiet");
+                let threads = args.opt_value_from_fn("--threads", parse_threads)?;
+                let timeout = args.opt_value_from_fn("--timeout", parse_duration)?;
+                let format = args.opt_value_from_str("--format")?;
+
+                let input = if let Some(path) = args.opt_value_from_str("--input")? {
+                    InputSource::File(path)
+                } else if args.contains("--stdin") {
+                    InputSource::Stdin
+                } else if args.contains("--example") {
+                    // NOTE: parsed last, the example number is the only free argument left.
+                    InputSource::Example(args.opt_free_from_str()?)
+                } else {
+                    InputSource::Puzzle
+                };
+
+                AppArguments::Solve {
+                    days,
+                    release,
+                    dhat,
+                    mem,
+                    quiet,
+                    threads,
+                    submit,
+                    timeout,
+                    format,
+                    input,
+                }
+            }
+            Some("migrate") => AppArguments::Migrate,
+            #[cfg(feature = "today")]
+            Some("today") => {
+                // the puzzle is scaffolded in the year of the current advent.
+                if year.is_some() {
+                    eprintln!(
+                        "`today` works on the year of the current puzzle and doesn't accept `--year`."
+                    );
+                    process::exit(1);
+                }
+                AppArguments::Today
+            }
             #[cfg(feature = "today")]
-            Some("today") => AppArguments::Today,
+            Some("wait") => {
+                // the puzzle is scaffolded in the year it unlocks in.
+                if year.is_some() {
+                    eprintln!(
+                        "`wait` works on the year of the next puzzle and doesn't accept `--year`."
+                    );
+                    process::exit(1);
+                }
+                AppArguments::Wait
+            }
             Some(x) => {
                 eprintln!("Unknown command: {x}");
                 process::exit(1);
@@ -91,54 +221,120 @@ mod args {
             eprintln!("Warning: unknown argument(s): {remaining:?}.");
         }
 
-        Ok(app_args)
+        Ok((app_args, year))
     }
 }
 
+/// Every day's solution compiled into this binary, see `build.rs`.
+// NOTE: DHAT brings a global allocator per day, and the tests of a day already run in its own binary.
+#[cfg(all(feature = "registry", not(feature = "dhat-heap"), not(test)))]
+mod solutions {
+    use advent_of_code::template::registry::Registered;
+
+    include!(concat!(env!("OUT_DIR"), "/solutions.rs"));
+}
+
+/// Counts the allocations of the solutions that run in this process with `--mem`.
+// NOTE: DHAT brings its own global allocator.
+#[cfg(all(feature = "registry", not(feature = "dhat-heap")))]
+#[global_allocator]
+static ALLOC: advent_of_code::template::memory::CountingAlloc =
+    advent_of_code::template::memory::CountingAlloc;
+
 fn main() {
+    #[cfg(all(feature = "registry", not(feature = "dhat-heap"), not(test)))]
+    advent_of_code::template::registry::register(solutions::SOLUTIONS);
+
     match parse() {
         Err(err) => {
             eprintln!("Error: {err}");
             std::process::exit(1);
         }
-        Ok(args) => match args {
-            AppArguments::All { release } => all::handle(release),
-            AppArguments::Time { day, all, store } => time::handle(day, all, store),
-            AppArguments::Download { day } => download::handle(day),
-            AppArguments::Read { day } => read::handle(day),
-            AppArguments::Scaffold {
-                day,
-                download,
-                overwrite,
-            } => {
-                scaffold::handle(day, overwrite);
-                if download {
+        Ok((args, year)) => {
+            if let Some(year) = year {
+                Year::select(year);
+            }
+            run(args);
+        }
+    };
+}
+
+fn run(args: AppArguments) {
+    match args {
+        AppArguments::All {
+            days,
+            release,
+            timeout,
+            mem,
+            threads,
+        } => all::handle(days.as_ref(), release, timeout, mem, threads),
+        AppArguments::Confirm { day, part } => confirm::handle(day, part),
+        AppArguments::Time {
+            days,
+            all,
+            store,
+            bench,
+            timeout,
+            mem,
+            threads,
+            scaling,
+        } => match days {
+            Some(days) if scaling => time::handle_scaling(&days, bench, timeout),
+            _ => time::handle(days.as_ref(), all, store, bench, timeout, mem, threads),
+        },
+        AppArguments::Download { day } => download::handle(day),
+        AppArguments::Migrate => migrate::handle(Year::selected()),
+        AppArguments::Read { day } => read::handle(day),
+        AppArguments::Scaffold {
+            day,
+            download,
+            overwrite,
+        } => {
+            scaffold::handle(day, overwrite);
+            if download {
+                download::handle(day);
+            }
+        }
+        AppArguments::Solve {
+            days,
+            release,
+            dhat,
+            mem,
+            quiet,
+            threads,
+            submit,
+            timeout,
+            format,
+            input,
+        } => solve::handle(
+            &days, release, dhat, mem, quiet, threads, submit, timeout, format, &input,
+        ),
+        #[cfg(feature = "today")]
+        AppArguments::Today => {
+            let now = SystemClock.now();
+            match unlock::current(now) {
+                Some((year, day)) => {
+                    Year::select(year);
+                    scaffold::handle(day, false);
                     download::handle(day);
+                    read::handle(day)
                 }
-            }
-            AppArguments::Solve {
-                day,
-                release,
-                dhat,
-                submit,
-            } => solve::handle(day, release, dhat, submit),
-            #[cfg(feature = "today")]
-            AppArguments::Today => {
-                match Day::today() {
-                    Some(day) => {
-                        scaffold::handle(day, false);
-                        download::handle(day);
-                        read::handle(day)
-                    }
-                    None => {
+                None => {
+                    eprintln!(
+                        "`today` command can only be run between the 1st of december and \
+                            the last day of advent. Please use `scaffold` with a specific day."
+                    );
+                    if let Some(next) = unlock::next(now) {
                         eprintln!(
-                            "`today` command can only be run between the 1st and \
-                            the 25th of december. Please use `scaffold` with a specific day."
+                            "The next puzzle unlocks in {}, use `wait` to get it as soon as it does.",
+                            unlock::format_countdown((next.at - now).to_std().unwrap_or_default())
                         );
-                        process::exit(1)
                     }
-                };
-            }
-        },
-    };
+                    process::exit(1)
+                }
+            };
+        }
+        #[cfg(feature = "today")]
+        AppArguments::Wait => wait::handle(&SystemClock),
+    }
 }
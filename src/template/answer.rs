@@ -0,0 +1,160 @@
+use std::fmt::Display;
+
+use crate::utils::{ocr, Grid};
+
+/// The answer to a solution part, displayed, checked against confirmed answers and submitted as its [`Display`] text.
+///
+/// Parts can return anything that converts into an answer: integers, strings or an [`Answer`] itself,
+/// e.g. a grid of letters created with [`Answer::from_grid`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Answer {
+    Number(i128),
+    Text(String),
+    /// Letters drawn on a grid, with the text recognized by [`ocr`] if the drawing uses a known font.
+    Grid {
+        art: String,
+        text: Option<String>,
+    },
+}
+
+impl Answer {
+    /// An answer drawn in `art`, one line per row of pixels. See [`ocr::recognize`].
+    pub fn drawing(art: impl Into<String>) -> Self {
+        let art = art.into();
+        let text = ocr::recognize(&art);
+        Answer::Grid { art, text }
+    }
+
+    /// An answer drawn on `grid`, where `is_lit` tells the lit cells apart.
+    pub fn from_grid<T>(grid: &Grid<T>, is_lit: impl Fn(&T) -> bool) -> Self {
+        Answer::Grid {
+            text: ocr::recognize_grid(grid, &is_lit),
+            art: ocr::render(grid, is_lit),
+        }
+    }
+
+    /// Whether the answer spans multiple lines, which is the case for drawings that can't be recognized.
+    pub fn is_multiline(&self) -> bool {
+        self.to_string().contains('\n')
+    }
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Answer::Number(n) => write!(f, "{n}"),
+            Answer::Text(text)
+            | Answer::Grid {
+                text: Some(text), ..
+            } => write!(f, "{text}"),
+            Answer::Grid { art, text: None } => write!(f, "{art}"),
+        }
+    }
+}
+
+macro_rules! impl_from_integer {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(value: $t) -> Self {
+                    Answer::Number(value.into())
+                }
+            }
+        )*
+    };
+}
+
+impl_from_integer!(u8, u16, u32, u64, i8, i16, i32, i64, i128);
+
+impl From<usize> for Answer {
+    fn from(value: usize) -> Self {
+        Answer::Number(value as i128)
+    }
+}
+
+impl From<isize> for Answer {
+    fn from(value: isize) -> Self {
+        Answer::Number(value as i128)
+    }
+}
+
+impl From<u128> for Answer {
+    fn from(value: u128) -> Self {
+        // NOTE: too large for a signed number, but still a valid answer.
+        i128::try_from(value).map_or_else(|_| Answer::Text(value.to_string()), Answer::Number)
+    }
+}
+
+impl From<String> for Answer {
+    fn from(value: String) -> Self {
+        Answer::Text(value)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(value: &str) -> Self {
+        Answer::Text(value.into())
+    }
+}
+
+impl From<char> for Answer {
+    fn from(value: char) -> Self {
+        Answer::Text(value.into())
+    }
+}
+
+/// Drawings with `#` for lit cells, e.g. parsed with [`Grid::parse`].
+impl From<&Grid<u8>> for Answer {
+    fn from(value: &Grid<u8>) -> Self {
+        Answer::from_grid(value, |&c| c == b'#')
+    }
+}
+
+impl From<&Grid<bool>> for Answer {
+    fn from(value: &Grid<bool>) -> Self {
+        Answer::from_grid(value, |&lit| lit)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Answer;
+    use crate::utils::Grid;
+
+    const HI: &str = "\
+#..#.###
+#..#..#.
+####..#.
+#..#..#.
+#..#..#.
+#..#.###";
+
+    #[test]
+    fn converts_answers() {
+        assert_eq!(Answer::from(42u32), Answer::Number(42));
+        assert_eq!(Answer::from(-7i64), Answer::Number(-7));
+        assert_eq!(Answer::from(3usize), Answer::Number(3));
+        assert_eq!(Answer::from(u128::MAX), Answer::Text(u128::MAX.to_string()));
+        assert_eq!(Answer::from("abc"), Answer::Text("abc".into()));
+        assert_eq!(Answer::from('x').to_string(), "x");
+    }
+
+    #[test]
+    fn recognizes_drawings() {
+        let answer = Answer::drawing(HI);
+        assert_eq!(answer.to_string(), "HI");
+        assert!(!answer.is_multiline());
+
+        let grid = Grid::parse(HI);
+        assert_eq!(Answer::from(&grid), answer);
+    }
+
+    #[test]
+    fn keeps_unrecognized_drawings() {
+        let answer = Answer::drawing("#.#\n.#.");
+        assert_eq!(answer.to_string(), "#.#\n.#.");
+        assert!(answer.is_multiline());
+    }
+}
@@ -0,0 +1,173 @@
+/// Confirmed answers to the real puzzle inputs, stored in `data/<year>/answers/DD.json`.
+/// Solutions are checked against them on every run, so a refactoring that changes an answer is noticed.
+use std::{collections::HashMap, fs, io::Error, path::PathBuf, str::FromStr};
+use tinyjson::JsonValue;
+
+use crate::template::{layout, Day};
+
+/// Outcome of comparing an answer with the confirmed one.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum AnswerCheck {
+    /// No answer has been confirmed for this part yet.
+    Unknown,
+    Correct,
+    /// The answer differs from the confirmed one.
+    Drifted {
+        expected: String,
+    },
+}
+
+/// Confirmed answers for a single day.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Answers {
+    pub part_1: Option<String>,
+    pub part_2: Option<String>,
+}
+
+impl Answers {
+    fn path(day: Day) -> PathBuf {
+        layout::data_path(&format!("answers/{day}.json"))
+    }
+
+    /// Rehydrate the answers of `day`. If not present, returns empty answers.
+    pub fn read(day: Day) -> Self {
+        fs::read_to_string(Self::path(day))
+            .map_err(|x| x.to_string())
+            .and_then(Answers::try_from)
+            .unwrap_or_default()
+    }
+
+    /// Dehydrate the answers of `day` to its JSON file.
+    pub fn store(&self, day: Day) -> Result<(), Error> {
+        let path = Self::path(day);
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let json = JsonValue::from(self.clone());
+        let mut file = fs::File::create(path)?;
+        json.format_to(&mut file)
+    }
+
+    pub fn get(&self, part: u8) -> Option<&str> {
+        match part {
+            1 => self.part_1.as_deref(),
+            2 => self.part_2.as_deref(),
+            _ => None,
+        }
+    }
+
+    pub fn set(&mut self, part: u8, answer: String) {
+        match part {
+            1 => self.part_1 = Some(answer),
+            2 => self.part_2 = Some(answer),
+            _ => panic!("invalid part: {part}"),
+        }
+    }
+
+    pub fn check(&self, part: u8, answer: &str) -> AnswerCheck {
+        match self.get(part) {
+            None => AnswerCheck::Unknown,
+            Some(expected) if expected == answer => AnswerCheck::Correct,
+            Some(expected) => AnswerCheck::Drifted {
+                expected: expected.into(),
+            },
+        }
+    }
+}
+
+/// Stores `answer` as the confirmed answer for a part of `day`, keeping the other part.
+pub fn confirm(day: Day, part: u8, answer: String) -> Result<(), Error> {
+    let mut answers = Answers::read(day);
+    answers.set(part, answer);
+    answers.store(day)
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<Answers> for JsonValue {
+    fn from(value: Answers) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        if let Some(part_1) = value.part_1 {
+            map.insert("part_1".into(), JsonValue::String(part_1));
+        }
+
+        if let Some(part_2) = value.part_2 {
+            map.insert("part_2".into(), JsonValue::String(part_2));
+        }
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for Answers {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).map_err(|x| x.to_string())?;
+        let json = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected answers to be a JSON object.")?;
+
+        let part = |key: &str| -> Result<Option<String>, String> {
+            json.get(key)
+                .map(|v| {
+                    v.get::<String>()
+                        .cloned()
+                        .ok_or(format!("Expected answers.{key} to be a string."))
+                })
+                .transpose()
+        };
+
+        Ok(Answers {
+            part_1: part("part_1")?,
+            part_2: part("part_2")?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{AnswerCheck, Answers};
+    use tinyjson::JsonValue;
+
+    #[test]
+    fn checks_answers() {
+        let answers = Answers {
+            part_1: Some("42".into()),
+            part_2: None,
+        };
+
+        assert_eq!(answers.check(1, "42"), AnswerCheck::Correct);
+        assert_eq!(
+            answers.check(1, "43"),
+            AnswerCheck::Drifted {
+                expected: "42".into()
+            }
+        );
+        assert_eq!(answers.check(2, "42"), AnswerCheck::Unknown);
+    }
+
+    #[test]
+    fn roundtrips_answers() {
+        let answers = Answers {
+            part_1: None,
+            part_2: Some("multi\nline".into()),
+        };
+
+        let json = JsonValue::from(answers.clone()).stringify().unwrap();
+        assert_eq!(Answers::try_from(json).unwrap(), answers);
+    }
+
+    #[test]
+    fn rejects_invalid_answers() {
+        assert!(Answers::try_from(r#"{ "part_1": 42 }"#.to_string()).is_err());
+        assert!(Answers::try_from("[]".to_string()).is_err());
+        assert_eq!(
+            Answers::try_from("{}".to_string()).unwrap(),
+            Answers::default()
+        );
+    }
+}
@@ -1,10 +1,12 @@
 /// Wrapper module around the "aoc-cli" command-line.
 use std::{
     fmt::Display,
+    fs,
+    path::Path,
     process::{Command, Output, Stdio},
 };
 
-use crate::template::Day;
+use crate::template::{layout, Day, Year};
 
 #[derive(Debug)]
 pub enum AocCommandError {
@@ -53,6 +55,13 @@ pub fn download(day: Day) -> Result<Output, AocCommandError> {
     let input_path = get_input_path(day);
     let puzzle_path = get_puzzle_path(day);
 
+    // aoc-cli doesn't create the directories of a year that has no data yet.
+    for path in [&input_path, &puzzle_path] {
+        if let Some(dir) = Path::new(path).parent() {
+            let _ = fs::create_dir_all(dir);
+        }
+    }
+
     let args = build_args(
         "download",
         &[
@@ -77,31 +86,41 @@ pub fn submit(day: Day, part: u8, result: &str) -> Result<Output, AocCommandErro
     let mut args = build_args("submit", &[], day);
     args.push(part.to_string());
     args.push(result.to_string());
-    call_aoc_cli(&args)
+
+    // capture stdout to parse the verdict, but still show it.
+    let output = Command::new("aoc")
+        .args(&args)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::inherit())
+        .output()
+        .map_err(|_| AocCommandError::CommandNotCallable)?;
+
+    print!("{}", String::from_utf8_lossy(&output.stdout));
+
+    if output.status.success() {
+        Ok(output)
+    } else {
+        Err(AocCommandError::BadExitStatus(output))
+    }
 }
 
 fn get_input_path(day: Day) -> String {
-    format!("data/inputs/{day}.txt")
+    layout::data_path(&format!("inputs/{day}.txt"))
+        .display()
+        .to_string()
 }
 
 fn get_puzzle_path(day: Day) -> String {
-    format!("data/puzzles/{day}.md")
-}
-
-fn get_year() -> Option<u16> {
-    match std::env::var("AOC_YEAR") {
-        Ok(x) => x.parse().ok().or(None),
-        Err(_) => None,
-    }
+    layout::data_path(&format!("puzzles/{day}.md"))
+        .display()
+        .to_string()
 }
 
 fn build_args(command: &str, args: &[String], day: Day) -> Vec<String> {
     let mut cmd_args = args.to_vec();
 
-    if let Some(year) = get_year() {
-        cmd_args.push("--year".into());
-        cmd_args.push(year.to_string());
-    }
+    cmd_args.push("--year".into());
+    cmd_args.push(Year::selected().to_string());
 
     cmd_args.append(&mut vec!["--day".into(), day.to_string(), command.into()]);
 
@@ -0,0 +1,332 @@
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::time::Duration;
+use tinyjson::JsonValue;
+
+use crate::template::runner::{format_duration_arg, parse_duration};
+
+/// Settings of a benchmark, enabled with `--time` and tuned with `--bench-time`, `--min-samples`,
+/// `--max-samples`, `--warmup` and `--filter-outliers`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BenchOptions {
+    /// Approximate time spent on sampling a part, used to derive the number of samples.
+    pub time: Duration,
+    pub min_samples: usize,
+    pub max_samples: usize,
+    /// Number of runs before sampling, [`None`] for a tenth of the samples.
+    pub warmup: Option<usize>,
+    pub filter_outliers: bool,
+}
+
+impl Default for BenchOptions {
+    fn default() -> Self {
+        Self {
+            time: Duration::from_secs(1),
+            min_samples: 10,
+            max_samples: 10000,
+            warmup: None,
+            filter_outliers: false,
+        }
+    }
+}
+
+/// Fraction of the samples that is run as warmup if no fixed number of warmup runs is set.
+const WARMUP_DIVISOR: usize = 10;
+
+impl BenchOptions {
+    /// Checks that the sample counts form a valid range.
+    pub fn validate(self) -> Result<Self, String> {
+        if self.min_samples == 0 {
+            return Err("`--min-samples` must be at least 1.".into());
+        }
+
+        if self.min_samples > self.max_samples {
+            return Err(format!(
+                "`--min-samples` ({}) must not exceed `--max-samples` ({}).",
+                self.min_samples, self.max_samples
+            ));
+        }
+
+        Ok(self)
+    }
+
+    /// Parses the options from the arguments of a solution binary, [`None`] without `--time`.
+    pub fn from_args(args: &[String]) -> Result<Option<Self>, String> {
+        if !args.iter().any(|x| x == "--time") {
+            return Ok(None);
+        }
+
+        let value = |name: &str| {
+            args.iter()
+                .position(|x| x == name)
+                .map(|i| {
+                    args.get(i + 1)
+                        .filter(|value| !value.starts_with("--"))
+                        .ok_or(format!("`{name}` expects a value."))
+                })
+                .transpose()
+        };
+
+        let count = |name: &str| {
+            value(name)?
+                .map(|value| {
+                    value
+                        .parse::<usize>()
+                        .map_err(|_| format!("`{name}` expects a number, got \"{value}\"."))
+                })
+                .transpose()
+        };
+
+        let defaults = Self::default();
+
+        Self {
+            time: match value("--bench-time")? {
+                Some(value) => parse_duration(value)?,
+                None => defaults.time,
+            },
+            min_samples: count("--min-samples")?.unwrap_or(defaults.min_samples),
+            max_samples: count("--max-samples")?.unwrap_or(defaults.max_samples),
+            warmup: count("--warmup")?.or(defaults.warmup),
+            filter_outliers: args.iter().any(|x| x == "--filter-outliers"),
+        }
+        .validate()
+        .map(Some)
+    }
+
+    /// Arguments that select these options in a solution binary.
+    pub fn to_args(&self) -> Vec<String> {
+        let mut args = vec![
+            "--time".into(),
+            "--bench-time".into(),
+            format_duration_arg(self.time),
+            "--min-samples".into(),
+            self.min_samples.to_string(),
+            "--max-samples".into(),
+            self.max_samples.to_string(),
+        ];
+
+        if let Some(warmup) = self.warmup {
+            args.extend(["--warmup".into(), warmup.to_string()]);
+        }
+
+        if self.filter_outliers {
+            args.push("--filter-outliers".into());
+        }
+
+        args
+    }
+
+    /// Number of samples to record for a part whose first run took `base_time`.
+    pub fn samples(&self, base_time: Duration) -> usize {
+        #[allow(clippy::cast_possible_truncation)]
+        let samples = (self.time.as_nanos() / base_time.as_nanos().max(10)) as usize;
+        samples.clamp(self.min_samples, self.max_samples)
+    }
+
+    /// Number of unrecorded runs before `samples` are recorded.
+    pub fn warmup_runs(&self, samples: usize) -> usize {
+        self.warmup
+            .unwrap_or_else(|| (samples / WARMUP_DIVISOR).max(1))
+    }
+}
+
+impl Display for BenchOptions {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{:?} per part, {}-{} samples, ",
+            self.time, self.min_samples, self.max_samples
+        )?;
+
+        match self.warmup {
+            Some(warmup) => write!(f, "{warmup} warmup runs")?,
+            None => write!(f, "{}% warmup", 100 / WARMUP_DIVISOR)?,
+        }
+
+        if self.filter_outliers {
+            write!(f, ", outliers filtered")?;
+        }
+
+        Ok(())
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[allow(clippy::cast_precision_loss)]
+impl From<&BenchOptions> for JsonValue {
+    fn from(value: &BenchOptions) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+        map.insert(
+            "time".into(),
+            JsonValue::String(format_duration_arg(value.time)),
+        );
+        map.insert(
+            "min_samples".into(),
+            JsonValue::Number(value.min_samples as f64),
+        );
+        map.insert(
+            "max_samples".into(),
+            JsonValue::Number(value.max_samples as f64),
+        );
+        map.insert(
+            "warmup".into(),
+            match value.warmup {
+                Some(warmup) => JsonValue::Number(warmup as f64),
+                None => JsonValue::Null,
+            },
+        );
+        map.insert(
+            "filter_outliers".into(),
+            JsonValue::Boolean(value.filter_outliers),
+        );
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for BenchOptions {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected bench to be a JSON object.")?;
+
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>().copied())
+                .map(|n| n as usize)
+                .ok_or(format!("Expected bench.{key} to be a number."))
+        };
+
+        let time = json
+            .get("time")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected bench.time to be a string.")?;
+
+        Ok(BenchOptions {
+            time: parse_duration(time)?,
+            min_samples: number("min_samples")?,
+            max_samples: number("max_samples")?,
+            warmup: match json.get("warmup") {
+                Some(v) if !v.is_null() => Some(number("warmup")?),
+                _ => None,
+            },
+            filter_outliers: json
+                .get("filter_outliers")
+                .and_then(|v| v.get::<bool>().copied())
+                .unwrap_or_default(),
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::BenchOptions;
+    use std::time::Duration;
+    use tinyjson::JsonValue;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn parses_bench_options() {
+        assert_eq!(BenchOptions::from_args(&args(&["01"])), Ok(None));
+        assert_eq!(
+            BenchOptions::from_args(&args(&["01", "--time"])),
+            Ok(Some(BenchOptions::default()))
+        );
+        assert_eq!(
+            BenchOptions::from_args(&args(&[
+                "01",
+                "--time",
+                "--bench-time",
+                "500ms",
+                "--max-samples",
+                "100",
+                "--warmup",
+                "3",
+                "--filter-outliers"
+            ])),
+            Ok(Some(BenchOptions {
+                time: Duration::from_millis(500),
+                min_samples: 10,
+                max_samples: 100,
+                warmup: Some(3),
+                filter_outliers: true,
+            }))
+        );
+        assert!(BenchOptions::from_args(&args(&["01", "--time", "--warmup"])).is_err());
+        assert!(BenchOptions::from_args(&args(&["01", "--time", "--min-samples", "x"])).is_err());
+        assert!(BenchOptions::from_args(&args(&["01", "--time", "--min-samples", "0"])).is_err());
+        assert!(BenchOptions::from_args(&args(&[
+            "01",
+            "--time",
+            "--min-samples",
+            "20",
+            "--max-samples",
+            "10"
+        ]))
+        .is_err());
+    }
+
+    #[test]
+    fn roundtrips_bench_options() {
+        let options = BenchOptions {
+            time: Duration::from_millis(1500),
+            min_samples: 5,
+            max_samples: 50,
+            warmup: Some(2),
+            filter_outliers: true,
+        };
+
+        assert_eq!(
+            BenchOptions::from_args(&options.to_args()),
+            Ok(Some(options))
+        );
+
+        let json = JsonValue::from(&options);
+        assert_eq!(BenchOptions::try_from(&json), Ok(options));
+        let json = JsonValue::from(&BenchOptions::default());
+        assert_eq!(BenchOptions::try_from(&json), Ok(BenchOptions::default()));
+    }
+
+    #[test]
+    fn derives_sample_counts() {
+        let options = BenchOptions::default();
+        assert_eq!(options.samples(Duration::from_millis(1)), 1000);
+        assert_eq!(options.samples(Duration::from_millis(500)), 10);
+        assert_eq!(options.samples(Duration::from_nanos(1)), 10000);
+        assert_eq!(options.warmup_runs(1000), 100);
+        assert_eq!(options.warmup_runs(5), 1);
+
+        let options = BenchOptions {
+            warmup: Some(0),
+            ..options
+        };
+        assert_eq!(options.warmup_runs(1000), 0);
+    }
+
+    #[test]
+    fn describes_bench_options() {
+        assert_eq!(
+            BenchOptions::default().to_string(),
+            "1s per part, 10-10000 samples, 10% warmup"
+        );
+
+        let options = BenchOptions {
+            time: Duration::from_millis(200),
+            warmup: Some(5),
+            filter_outliers: true,
+            ..BenchOptions::default()
+        };
+        assert_eq!(
+            options.to_string(),
+            "200ms per part, 10-10000 samples, 5 warmup runs, outliers filtered"
+        );
+    }
+}
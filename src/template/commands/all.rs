@@ -1,5 +1,26 @@
-use crate::template::{all_days, run_multi::run_multi};
+use std::process;
+use std::time::Duration;
 
-pub fn handle(is_release: bool) {
-    run_multi(&all_days().collect(), is_release, false);
+use crate::template::{all_days, run_multi::run_multi, DaySet};
+
+/// Runs the solutions of `days`, or of every day if [`None`].
+pub fn handle(
+    days: Option<&DaySet>,
+    is_release: bool,
+    timeout: Option<Duration>,
+    track_memory: bool,
+    threads: Option<usize>,
+) {
+    let summary = run_multi(
+        &days.map_or_else(|| all_days().collect(), DaySet::days),
+        is_release,
+        None,
+        timeout,
+        track_memory,
+        threads,
+    );
+
+    if summary.failed {
+        process::exit(1);
+    }
 }
@@ -0,0 +1,54 @@
+use std::process;
+
+use crate::template::answers::Answers;
+use crate::template::run_multi::child_commands;
+use crate::template::Day;
+
+/// Runs the solution of `day` and stores its answers as the confirmed answers.
+/// If `part` is given, only the answer of that part is stored.
+pub fn handle(day: Day, part: Option<u8>) {
+    let reports = match child_commands::run_solution(day, None, true, None, None, false, None) {
+        Ok(Some(output)) if !output.timed_out => output.reports,
+        _ => {
+            eprintln!("Could not run the solution for day {day}.");
+            process::exit(1);
+        }
+    };
+
+    let mut answers = Answers::read(day);
+    let mut confirmed = vec![];
+
+    for report in reports.into_iter().filter(|report| report.day == day) {
+        let report_part = match report.name.as_str() {
+            "part_1" => 1,
+            "part_2" => 2,
+            _ => continue,
+        };
+
+        if part.is_some_and(|part| part != report_part) {
+            continue;
+        }
+
+        if let Some(answer) = report.answer {
+            answers.set(report_part, answer);
+            confirmed.push(report_part);
+        }
+    }
+
+    if confirmed.is_empty() {
+        eprintln!("No answers to confirm for day {day}.");
+        process::exit(1);
+    }
+
+    match answers.store(day) {
+        Ok(()) => {
+            for part in confirmed {
+                println!("✔ Confirmed answer of day {day} part {part}.");
+            }
+        }
+        Err(e) => {
+            eprintln!("Failed to store answers: {e}");
+            process::exit(1);
+        }
+    }
+}
@@ -0,0 +1,70 @@
+use std::{fs, path::Path, process};
+
+use crate::template::{layout, readme_benchmarks, Year};
+
+/// Moves the files of the layout without years to the layout of `year`, see [`layout`].
+/// Files that already exist in the layout of `year` are kept and reported.
+pub fn handle(year: Year) {
+    let files = match layout::legacy_files(year) {
+        Ok(files) => files,
+        Err(e) => {
+            eprintln!("Failed to list the files to migrate: {e}");
+            process::exit(1);
+        }
+    };
+
+    let mut moved = 0;
+    let mut skipped = 0;
+
+    for (from, to) in &files {
+        if to.exists() {
+            eprintln!(
+                "Skipped \"{}\", \"{}\" already exists.",
+                from.display(),
+                to.display()
+            );
+            skipped += 1;
+            continue;
+        }
+
+        let renamed = to
+            .parent()
+            .map_or(Ok(()), fs::create_dir_all)
+            .and_then(|()| fs::rename(from, to));
+
+        match renamed {
+            Ok(()) => {
+                println!("Moved \"{}\" to \"{}\"", from.display(), to.display());
+                moved += 1;
+            }
+            Err(e) => {
+                eprintln!("Failed to move \"{}\": {e}", from.display());
+                process::exit(1);
+            }
+        }
+    }
+
+    // NOTE: only succeeds for folders that are empty now.
+    for (from, _) in &files {
+        if let Some(dir) = from.parent().filter(|dir| *dir != Path::new("src/bin")) {
+            let _ = fs::remove_dir(dir);
+        }
+    }
+
+    match readme_benchmarks::migrate(year) {
+        Ok(true) => println!("Marked the benchmarks in the readme as the benchmarks of {year}."),
+        Ok(false) => {}
+        Err(_) => eprintln!("Failed to migrate the benchmarks in the readme."),
+    }
+
+    println!("---");
+    if moved == 0 && skipped == 0 {
+        println!("🎄 Nothing to migrate, the files of {year} already have a year.");
+    } else {
+        println!("🎄 Migrated {moved} files to {year}.");
+    }
+
+    if skipped > 0 {
+        process::exit(1);
+    }
+}
@@ -1,6 +1,10 @@
 pub mod all;
+pub mod confirm;
 pub mod download;
+pub mod migrate;
 pub mod read;
 pub mod scaffold;
 pub mod solve;
 pub mod time;
+#[cfg(feature = "today")]
+pub mod wait;
@@ -1,9 +1,39 @@
-use std::process::{Command, Stdio};
+use std::process::{self, Command, Stdio};
+use std::time::Duration;
 
-use crate::template::Day;
+use crate::template::runner::{format_duration_arg, Format};
+use crate::template::{all_days, layout, DaySet, InputSource, ANSI_BOLD, ANSI_RESET};
 
-pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
-    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
+/// Runs the solution binaries of `days` one after another.
+#[allow(clippy::too_many_arguments)]
+pub fn handle(
+    days: &DaySet,
+    release: bool,
+    dhat: bool,
+    track_memory: bool,
+    quiet: bool,
+    threads: Option<usize>,
+    submit_part: Option<u8>,
+    timeout: Option<Duration>,
+    format: Option<Format>,
+    input: &InputSource,
+) {
+    let selected = days.days();
+    let days: Vec<_> = all_days().filter(|day| selected.contains(day)).collect();
+
+    if days.is_empty() {
+        eprintln!("No days selected.");
+        process::exit(1);
+    }
+
+    // an answer is submitted for a single day, and a single input can't be the input of several days.
+    let single_input = matches!(input, InputSource::File(_) | InputSource::Stdin);
+    if days.len() > 1 && (submit_part.is_some() || single_input) {
+        eprintln!("`--submit`, `--input` and `--stdin` can only be used with a single day.");
+        process::exit(1);
+    }
+
+    let mut cmd_args = vec![];
 
     if dhat {
         cmd_args.extend([
@@ -18,17 +48,63 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
 
     cmd_args.push("--".to_string());
 
+    if track_memory {
+        cmd_args.push("--mem".to_string());
+    }
+
+    if quiet {
+        cmd_args.push("--quiet".to_string());
+    }
+
+    if let Some(threads) = threads {
+        cmd_args.push("--threads".to_string());
+        cmd_args.push(threads.to_string());
+    }
+
     if let Some(submit_part) = submit_part {
         cmd_args.push("--submit".to_string());
         cmd_args.push(submit_part.to_string());
     }
 
-    let mut cmd = Command::new("cargo")
-        .args(&cmd_args)
-        .stdout(Stdio::inherit())
-        .stderr(Stdio::inherit())
-        .spawn()
-        .unwrap();
+    if let Some(timeout) = timeout {
+        cmd_args.push("--timeout".to_string());
+        cmd_args.push(format_duration_arg(timeout));
+    }
+
+    if let Some(format) = format {
+        cmd_args.push("--format".to_string());
+        cmd_args.push(format.to_string());
+    }
+
+    cmd_args.extend(input.to_args());
 
-    cmd.wait().unwrap();
+    for (i, day) in days.iter().enumerate() {
+        if days.len() > 1 {
+            if i > 0 {
+                println!();
+            }
+            println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+            println!("------");
+
+            if !layout::bin_path(*day).exists() {
+                println!("Not solved.");
+                continue;
+            }
+        }
+
+        let bin_args = [
+            "run".to_string(),
+            "--bin".to_string(),
+            layout::bin_name(*day),
+        ];
+
+        let mut cmd = Command::new("cargo")
+            .args(bin_args.iter().chain(&cmd_args))
+            .stdout(Stdio::inherit())
+            .stderr(Stdio::inherit())
+            .spawn()
+            .unwrap();
+
+        cmd.wait().unwrap();
+    }
 }
@@ -1,13 +1,24 @@
 use std::collections::HashSet;
+use std::process;
+use std::time::Duration;
 
 use crate::template::run_multi::run_multi;
+use crate::template::scaling::run_scaling;
 use crate::template::timings::Timings;
-use crate::template::{all_days, readme_benchmarks, Day};
+use crate::template::{all_days, readme_benchmarks, BenchOptions, DaySet};
 
-pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
+pub fn handle(
+    days: Option<&DaySet>,
+    run_all: bool,
+    store: bool,
+    bench: BenchOptions,
+    timeout: Option<Duration>,
+    track_memory: bool,
+    threads: Option<usize>,
+) {
     let stored_timings = Timings::read_from_file();
 
-    let days_to_run = day.map_or_else(
+    let days_to_run: HashSet<_> = days.map_or_else(
         || {
             if run_all {
                 all_days().collect()
@@ -18,10 +29,18 @@ pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
                     .collect()
             }
         },
-        |day| HashSet::from([day]),
+        DaySet::days,
     );
 
-    let timings = run_multi(&days_to_run, true, true).unwrap();
+    let summary = run_multi(
+        &days_to_run,
+        true,
+        Some(bench),
+        timeout,
+        track_memory,
+        threads,
+    );
+    let timings = summary.timings.unwrap();
 
     if store {
         let merged_timings = stored_timings.merge(&timings);
@@ -37,4 +56,21 @@ pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
             }
         }
     }
+
+    if summary.failed {
+        process::exit(1);
+    }
+}
+
+/// Benches a single day with an increasing number of threads instead of storing its timings, see [`run_scaling`].
+pub fn handle_scaling(days: &DaySet, bench: BenchOptions, timeout: Option<Duration>) {
+    let days = days.days();
+    let [day] = days.iter().collect::<Vec<_>>()[..] else {
+        eprintln!("`--scaling` needs a single day, e.g. `cargo time 7 --scaling`.");
+        process::exit(1);
+    };
+
+    if run_scaling(*day, bench, timeout) {
+        process::exit(1);
+    }
 }
@@ -0,0 +1,82 @@
+use std::io::{self, IsTerminal, Write};
+use std::process;
+
+use crate::template::commands::{read, scaffold};
+use crate::template::unlock::{self, Clock};
+use crate::template::{aoc_cli, Year};
+
+/// Attempts to download the puzzle, it can take a moment until the server has it ready.
+const DOWNLOAD_ATTEMPTS: u32 = 8;
+
+/// Waits for the next puzzle to unlock, then scaffolds, downloads and reads it in the year it unlocks in.
+/// No other year may be selected before, `--year` is rejected for this command.
+pub fn handle(clock: &impl Clock) {
+    if aoc_cli::check().is_err() {
+        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
+        process::exit(1);
+    }
+
+    let Some(next) = unlock::next(clock.now()) else {
+        eprintln!("Failed to find the next puzzle unlock.");
+        process::exit(1);
+    };
+    Year::select(next.year);
+    if Year::selected() != next.year {
+        eprintln!(
+            "The next puzzle unlocks in {}, but {} is selected.",
+            next.year,
+            Year::selected()
+        );
+        process::exit(1);
+    }
+    let day = next.day;
+
+    println!(
+        "⏳ Day {day} of {} unlocks at {}.",
+        next.year,
+        next.at
+            .with_timezone(&chrono::Local)
+            .format("%Y-%m-%d %H:%M:%S")
+    );
+
+    // a countdown that redraws itself only works in a terminal.
+    let is_terminal = io::stdout().is_terminal();
+    let mut is_first_tick = true;
+    unlock::wait_until(clock, next.at, |left| {
+        if is_terminal {
+            print!("\r\x1b[2K⏳ {}", unlock::format_countdown(left));
+            let _ = io::stdout().flush();
+        } else if is_first_tick {
+            println!("⏳ {} left.", unlock::format_countdown(left));
+        }
+        is_first_tick = false;
+    });
+    if is_terminal {
+        println!("\r\x1b[2K🎄 Day {day} is unlocked!");
+    } else {
+        println!("🎄 Day {day} is unlocked!");
+    }
+
+    scaffold::handle(day, false);
+
+    let downloaded = unlock::retry(
+        clock,
+        DOWNLOAD_ATTEMPTS,
+        || aoc_cli::download(day),
+        |e, delay| match delay {
+            Some(delay) => eprintln!(
+                "Failed to download the puzzle: {e} Retrying in {}s...",
+                delay.as_secs()
+            ),
+            None => eprintln!("Failed to download the puzzle: {e}"),
+        },
+    );
+    if downloaded.is_err() {
+        eprintln!(
+            "Giving up after {DOWNLOAD_ATTEMPTS} attempts, try `cargo download {day}` later."
+        );
+        process::exit(1);
+    }
+
+    read::handle(day);
+}
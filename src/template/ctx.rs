@@ -0,0 +1,202 @@
+/// Instrumentation for solution parts that can stay in the code permanently.
+/// A part opts in by taking a `&Ctx` as second parameter:
+///
+/// ```ignore
+/// use advent_of_code::{debug, template::Ctx};
+///
+/// pub fn part_two(input: &str, ctx: &Ctx) -> Option<u32> {
+///     let grid = Grid::parse(input);
+///     debug!(ctx, "grid is {}x{}", grid.width, grid.height);
+///     ctx.frame(&grid);
+///     Some(ctx.progress(0..1000).filter(|i| i % 7 == 0).count() as u32)
+/// }
+/// ```
+///
+/// The context is only active during the first run of a part and without `--quiet`.
+/// While benching, all of its methods are no-ops.
+use std::fmt::{Arguments, Display};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+
+use indicatif::{ProgressBar, ProgressBarIter, ProgressFinish, ProgressIterator};
+
+use crate::template::runner::{format, Format};
+use crate::template::{ANSI_ITALIC, ANSI_RESET};
+
+/// Handle passed to solution parts that take one. The [`Default`] context is inactive, e.g. for tests.
+#[derive(Debug, Default)]
+pub struct Ctx {
+    active: AtomicBool,
+    frames: Mutex<Vec<String>>,
+}
+
+impl Ctx {
+    pub fn new(active: bool) -> Self {
+        Self {
+            active: AtomicBool::new(active),
+            frames: Mutex::new(vec![]),
+        }
+    }
+
+    /// Whether output is printed and frames are recorded.
+    pub fn is_active(&self) -> bool {
+        self.active.load(Ordering::Relaxed)
+    }
+
+    /// Turns the context into a no-op, e.g. before benching.
+    pub fn deactivate(&self) {
+        self.active.store(false, Ordering::Relaxed);
+    }
+
+    /// Prints a debug message, use the [`debug!`](crate::debug) macro to only format it if active.
+    pub fn debug(&self, message: Arguments) {
+        if !self.is_active() {
+            return;
+        }
+
+        let line = format!("{ANSI_ITALIC}{message}{ANSI_RESET}");
+        if format() == Format::Json {
+            eprintln!("{line}");
+        } else {
+            println!("{line}");
+        }
+    }
+
+    /// Shows a progress bar on stderr while `iter` is consumed.
+    /// Iterators without an exact length show a spinner instead.
+    pub fn progress<I: IntoIterator>(&self, iter: I) -> Progress<I::IntoIter> {
+        let iter = iter.into_iter();
+
+        if !self.is_active() {
+            return Progress::Hidden(iter);
+        }
+
+        let bar = match iter.size_hint() {
+            (lower, Some(upper)) if lower == upper => ProgressBar::new(lower as u64),
+            _ => ProgressBar::new_spinner(),
+        };
+
+        Progress::Shown(iter.progress_with(bar.with_finish(ProgressFinish::AndClear)))
+    }
+
+    /// Records a frame, e.g. a [`Grid`](crate::utils::Grid) after each step of a simulation.
+    pub fn frame(&self, frame: impl Display) {
+        if self.is_active() {
+            self.frames.lock().unwrap().push(frame.to_string());
+        }
+    }
+
+    /// Takes the frames recorded so far.
+    pub fn take_frames(&self) -> Vec<String> {
+        std::mem::take(&mut *self.frames.lock().unwrap())
+    }
+}
+
+/// An iterator wrapped by [`Ctx::progress`].
+pub enum Progress<I> {
+    Shown(ProgressBarIter<I>),
+    Hidden(I),
+}
+
+impl<I: Iterator> Iterator for Progress<I> {
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self {
+            Progress::Shown(iter) => iter.next(),
+            Progress::Hidden(iter) => iter.next(),
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match self {
+            Progress::Shown(iter) => iter.size_hint(),
+            Progress::Hidden(iter) => iter.size_hint(),
+        }
+    }
+}
+
+/// Prints a debug message with a [`Ctx`], like `println!`. The message is only formatted if the context is active.
+#[macro_export]
+macro_rules! debug {
+    ($ctx:expr, $($arg:tt)*) => {{
+        let ctx: &$crate::template::Ctx = $ctx;
+        if ctx.is_active() {
+            ctx.debug(format_args!($($arg)*));
+        }
+    }};
+}
+
+/// Solution parts with or without a [`Ctx`] parameter. The marker types [`Plain`] and [`WithCtx`]
+/// tell the two kinds of functions apart, so both can be passed to the runner.
+pub trait Part<I, Marker>: Sync {
+    type Output;
+
+    fn call(&self, input: I, ctx: &Ctx) -> Option<Self::Output>;
+}
+
+/// Marker for parts like `fn part_one(input: &str) -> Option<u32>`.
+pub struct Plain;
+
+/// Marker for parts like `fn part_one(input: &str, ctx: &Ctx) -> Option<u32>`.
+pub struct WithCtx;
+
+impl<I, T, F> Part<I, Plain> for F
+where
+    F: Fn(I) -> Option<T> + Sync,
+{
+    type Output = T;
+
+    fn call(&self, input: I, _ctx: &Ctx) -> Option<T> {
+        self(input)
+    }
+}
+
+impl<I, T, F> Part<I, WithCtx> for F
+where
+    F: Fn(I, &Ctx) -> Option<T> + Sync,
+{
+    type Output = T;
+
+    fn call(&self, input: I, ctx: &Ctx) -> Option<T> {
+        self(input, ctx)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Ctx, Part};
+
+    fn plain(input: &str) -> Option<usize> {
+        Some(input.len())
+    }
+
+    fn with_ctx(input: &str, ctx: &Ctx) -> Option<usize> {
+        ctx.frame(input);
+        Some(ctx.progress(input.chars()).count())
+    }
+
+    fn call<M>(part: impl Part<&'static str, M, Output = usize>, ctx: &Ctx) -> Option<usize> {
+        part.call("abc", ctx)
+    }
+
+    #[test]
+    fn calls_parts_with_and_without_ctx() {
+        let ctx = Ctx::default();
+        assert_eq!(call(plain, &ctx), Some(3));
+        assert_eq!(call(with_ctx, &ctx), Some(3));
+    }
+
+    #[test]
+    fn records_frames_while_active() {
+        let ctx = Ctx::new(true);
+        ctx.frame("first");
+        ctx.deactivate();
+        ctx.frame("second");
+        assert!(!ctx.is_active());
+        assert_eq!(ctx.take_frames(), vec!["first".to_string()]);
+        assert!(ctx.take_frames().is_empty());
+    }
+}
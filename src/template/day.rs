@@ -2,12 +2,6 @@ use std::error::Error;
 use std::fmt::Display;
 use std::str::FromStr;
 
-#[cfg(feature = "today")]
-use chrono::{Datelike, FixedOffset, Utc};
-
-#[cfg(feature = "today")]
-const SERVER_UTC_OFFSET: i32 = -5;
-
 /// A valid day number of advent (i.e. an integer in range 1 to 25).
 ///
 /// # Display
@@ -45,15 +39,10 @@ impl Day {
 
 #[cfg(feature = "today")]
 impl Day {
-    /// Returns the current day if it's between the 1st and the 25th of december, `None` otherwise.
+    /// Returns the current day if it's between the 1st of december and the last day of advent, `None` otherwise.
     pub fn today() -> Option<Self> {
-        let offset = FixedOffset::east_opt(SERVER_UTC_OFFSET * 3600)?;
-        let today = Utc::now().with_timezone(&offset);
-        if today.month() == 12 && today.day() <= 25 {
-            Self::new(u8::try_from(today.day()).ok()?)
-        } else {
-            None
-        }
+        let (_, day) = crate::template::unlock::current(chrono::Utc::now())?;
+        Some(day)
     }
 }
 
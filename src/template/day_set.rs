@@ -0,0 +1,336 @@
+use std::collections::HashSet;
+use std::error::Error;
+use std::fmt::Display;
+use std::str::FromStr;
+use std::time::Duration;
+
+use crate::template::answers::Answers;
+use crate::template::runner::parse_duration;
+use crate::template::timings::Timings;
+use crate::template::{all_days, Day, Year};
+
+/// Days that take longer than this in total are `slow`, unless another threshold is given like `slow>1s`.
+const DEFAULT_SLOW_THRESHOLD: Duration = Duration::from_millis(100);
+
+/// A set of days selected by an expression of comma-separated terms, e.g. `1-5,7,10-` or `unsolved,!25`.
+///
+/// - `7` is a single day, `1-5` a range of days and `10-` every day from the 10th.
+/// - `unsolved` are the days without a confirmed answer for both parts, see [`Answers`].
+///   The last day of a year has only one part, it is solved with a confirmed answer for part 1.
+/// - `slow` are the days whose stored timings take longer than 100ms in total, `slow>1s` sets another threshold.
+/// - `!` removes the days of a term, e.g. `1-10,!6`. An expression that starts with `!` removes them from all days.
+///
+/// The terms are applied from left to right. Only the days of the selected year are selected,
+/// e.g. `10-` stops at the 12th from 2025 on, see [`Year::last_day`].
+///
+/// ```
+/// # use advent_of_code::template::DaySet;
+/// let days: DaySet = "1-5,!3".parse().unwrap();
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DaySet {
+    terms: Vec<(bool, Selector)>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Selector {
+    Range(Day, Day),
+    Unsolved,
+    Slow(Duration),
+}
+
+impl DaySet {
+    /// Resolves the set, reading the confirmed answers and timings of the selected year if needed.
+    pub fn days(&self) -> HashSet<Day> {
+        let has = |selector: fn(&Selector) -> bool| self.terms.iter().any(|(_, s)| selector(s));
+        let last_day = Year::selected().last_day();
+
+        let solved: HashSet<Day> = if has(|s| matches!(s, Selector::Unsolved)) {
+            all_days()
+                .filter(|day| is_solved(&Answers::read(*day), *day, last_day))
+                .collect()
+        } else {
+            HashSet::new()
+        };
+        let timings = if has(|s| matches!(s, Selector::Slow(_))) {
+            Timings::read_from_file()
+        } else {
+            Timings::default()
+        };
+
+        self.resolve(
+            last_day,
+            |day| solved.contains(&day),
+            |day| {
+                timings
+                    .data
+                    .iter()
+                    .find(|timing| timing.day == day)
+                    .map(|timing| Duration::from_nanos(timing.total_nanos as u64))
+            },
+        )
+    }
+
+    fn resolve(
+        &self,
+        last_day: Day,
+        is_solved: impl Fn(Day) -> bool,
+        total_time: impl Fn(Day) -> Option<Duration>,
+    ) -> HashSet<Day> {
+        let days_of_year = || all_days().take_while(|day| *day <= last_day);
+
+        let mut days: HashSet<Day> = match self.terms.first() {
+            Some((false, _)) => days_of_year().collect(),
+            _ => HashSet::new(),
+        };
+
+        for (include, selector) in &self.terms {
+            let selected = days_of_year().filter(|day| match selector {
+                Selector::Range(start, end) => start <= day && day <= end,
+                Selector::Unsolved => !is_solved(*day),
+                Selector::Slow(threshold) => total_time(*day).is_some_and(|time| time > *threshold),
+            });
+
+            for day in selected {
+                if *include {
+                    days.insert(day);
+                } else {
+                    days.remove(&day);
+                }
+            }
+        }
+
+        days
+    }
+}
+
+/// Whether both parts of `day` have a confirmed answer. The last day of a year only has part 1.
+fn is_solved(answers: &Answers, day: Day, last_day: Day) -> bool {
+    answers.get(1).is_some() && (day == last_day || answers.get(2).is_some())
+}
+
+impl From<Day> for DaySet {
+    fn from(day: Day) -> Self {
+        DaySet {
+            terms: vec![(true, Selector::Range(day, day))],
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl FromStr for DaySet {
+    type Err = DaySetFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let terms = s
+            .split(',')
+            .map(|term| {
+                let term = term.trim();
+                match term.strip_prefix('!') {
+                    Some(term) => Ok((false, parse_selector(term.trim())?)),
+                    None => Ok((true, parse_selector(term)?)),
+                }
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(DaySet { terms })
+    }
+}
+
+fn parse_selector(term: &str) -> Result<Selector, DaySetFromStrError> {
+    if term.is_empty() {
+        return Err(DaySetFromStrError::Empty);
+    }
+
+    if term == "unsolved" {
+        return Ok(Selector::Unsolved);
+    }
+
+    if let Some(threshold) = term.strip_prefix("slow") {
+        return match threshold.strip_prefix('>') {
+            Some(threshold) => parse_duration(threshold)
+                .map(Selector::Slow)
+                .map_err(|_| DaySetFromStrError::InvalidThreshold(threshold.into())),
+            None if threshold.is_empty() => Ok(Selector::Slow(DEFAULT_SLOW_THRESHOLD)),
+            None => Err(DaySetFromStrError::UnknownTerm(term.into())),
+        };
+    }
+
+    if !term.starts_with(|c: char| c.is_ascii_digit()) {
+        return Err(DaySetFromStrError::UnknownTerm(term.into()));
+    }
+
+    let parse_day = |day: &str| {
+        day.trim()
+            .parse::<Day>()
+            .map_err(|_| DaySetFromStrError::InvalidDay(day.trim().into()))
+    };
+
+    let (start, end) = match term.split_once('-') {
+        Some((start, end)) if end.trim().is_empty() => (parse_day(start)?, crate::day!(25)),
+        Some((start, end)) => (parse_day(start)?, parse_day(end)?),
+        None => (parse_day(term)?, parse_day(term)?),
+    };
+
+    if start > end {
+        return Err(DaySetFromStrError::ReversedRange(term.into()));
+    }
+
+    Ok(Selector::Range(start, end))
+}
+
+/// An error which can be returned when parsing a [`DaySet`].
+#[derive(Debug, PartialEq, Eq)]
+pub enum DaySetFromStrError {
+    /// The expression or one of its terms is empty, e.g. `1,,3`.
+    Empty,
+    /// A term that isn't a day, a range or a keyword, e.g. `solved`.
+    UnknownTerm(String),
+    /// A day out of range, e.g. `26`.
+    InvalidDay(String),
+    /// A range that ends before it starts, e.g. `10-5`.
+    ReversedRange(String),
+    /// The threshold of `slow>..` isn't a duration.
+    InvalidThreshold(String),
+}
+
+impl Error for DaySetFromStrError {}
+
+impl Display for DaySetFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DaySetFromStrError::Empty => {
+                f.write_str("expecting days separated by commas, e.g. `1-5,7,10-`")
+            }
+            DaySetFromStrError::UnknownTerm(term) => write!(
+                f,
+                "unknown term `{term}`, expecting a day, a range like `1-5` or `10-`, `unsolved` or `slow`"
+            ),
+            DaySetFromStrError::InvalidDay(day) => {
+                write!(f, "invalid day `{day}`, expecting a day number between 1 and 25")
+            }
+            DaySetFromStrError::ReversedRange(range) => {
+                write!(f, "invalid range `{range}`, it ends before it starts")
+            }
+            DaySetFromStrError::InvalidThreshold(threshold) => write!(
+                f,
+                "invalid threshold `{threshold}`, expecting a duration like `slow>500ms`"
+            ),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{is_solved, DaySet, DaySetFromStrError};
+    use crate::day;
+    use crate::template::answers::Answers;
+    use crate::template::Day;
+    use std::time::Duration;
+
+    fn days(expression: &str) -> Vec<u8> {
+        days_until(expression, day!(25))
+    }
+
+    fn days_until(expression: &str, last_day: Day) -> Vec<u8> {
+        let set: DaySet = expression.parse().unwrap();
+        // day 1 and 4 are solved, day 2 and 3 are timed.
+        let mut days: Vec<u8> = set
+            .resolve(
+                last_day,
+                |day| day == 1 || day == 4,
+                |day| match day.into_inner() {
+                    2 => Some(Duration::from_millis(50)),
+                    3 => Some(Duration::from_secs(2)),
+                    _ => None,
+                },
+            )
+            .into_iter()
+            .map(|day| day.into_inner())
+            .collect();
+        days.sort_unstable();
+        days
+    }
+
+    #[test]
+    fn resolves_ranges() {
+        assert_eq!(days("7"), vec![7]);
+        assert_eq!(days("1-3,7,23-"), vec![1, 2, 3, 7, 23, 24, 25]);
+        assert_eq!(days(" 05 - 6 , 3"), vec![3, 5, 6]);
+        assert_eq!(days("1-5,!3"), vec![1, 2, 4, 5]);
+        assert_eq!(days("!2-24").len(), 2);
+        assert_eq!(days("!6").len(), 24);
+        // terms apply from left to right.
+        assert_eq!(days("1-3,!2,2"), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn resolves_keywords() {
+        assert_eq!(days("unsolved,!5-"), vec![2, 3]);
+        assert_eq!(days("slow"), vec![3]);
+        assert_eq!(days("slow>10ms"), vec![2, 3]);
+        assert_eq!(days("1-4,!slow"), vec![1, 2, 4]);
+    }
+
+    #[test]
+    fn resolves_days_of_short_years() {
+        assert_eq!(days_until("10-", day!(12)), vec![10, 11, 12]);
+        assert_eq!(days_until("!1-10", day!(12)), vec![11, 12]);
+        assert_eq!(days_until("unsolved", day!(12)).len(), 10);
+        assert_eq!(days_until("20", day!(12)), vec![]);
+    }
+
+    #[test]
+    fn solves_last_day_with_one_part() {
+        let mut answers = Answers::default();
+        assert!(!is_solved(&answers, day!(25), day!(25)));
+
+        answers.set(1, "42".into());
+        assert!(is_solved(&answers, day!(25), day!(25)));
+        assert!(is_solved(&answers, day!(12), day!(12)));
+        assert!(!is_solved(&answers, day!(12), day!(25)));
+
+        answers.set(2, "43".into());
+        assert!(is_solved(&answers, day!(12), day!(25)));
+    }
+
+    #[test]
+    fn converts_days() {
+        assert_eq!(DaySet::from(day!(7)), "7".parse().unwrap());
+    }
+
+    #[test]
+    fn reports_parse_errors() {
+        let error = |expression: &str| expression.parse::<DaySet>().unwrap_err();
+
+        assert_eq!(error(""), DaySetFromStrError::Empty);
+        assert_eq!(error("1,,3"), DaySetFromStrError::Empty);
+        assert_eq!(error("!"), DaySetFromStrError::Empty);
+        assert_eq!(
+            error("solved"),
+            DaySetFromStrError::UnknownTerm("solved".into())
+        );
+        assert_eq!(
+            error("slowest"),
+            DaySetFromStrError::UnknownTerm("slowest".into())
+        );
+        assert_eq!(error("26"), DaySetFromStrError::InvalidDay("26".into()));
+        assert_eq!(error("0-3"), DaySetFromStrError::InvalidDay("0".into()));
+        assert_eq!(error("3-x"), DaySetFromStrError::InvalidDay("x".into()));
+        assert_eq!(
+            error("10-5"),
+            DaySetFromStrError::ReversedRange("10-5".into())
+        );
+        assert_eq!(
+            error("slow>fast"),
+            DaySetFromStrError::InvalidThreshold("fast".into())
+        );
+        assert_eq!(
+            error("10-5").to_string(),
+            "invalid range `10-5`, it ends before it starts"
+        );
+    }
+}
@@ -0,0 +1,185 @@
+use std::fmt::Display;
+use std::io::{self, Read};
+use std::path::PathBuf;
+use std::{env, fs};
+
+use crate::template::{layout, Day};
+
+/// The input a solution is run on, selected with `--input <path>`, `--stdin` or `--example [N]`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum InputSource {
+    /// The real puzzle input in `data/<year>/inputs`.
+    #[default]
+    Puzzle,
+    /// An example in `data/<year>/examples`, optionally a numbered one. Example 1 is `01.txt`, the others are like `01-2.txt`.
+    Example(Option<u8>),
+    File(PathBuf),
+    Stdin,
+}
+
+impl InputSource {
+    /// Parses the input source from the arguments of a solution binary.
+    pub fn from_args(args: &[String]) -> Result<Self, String> {
+        let mut sources = vec![];
+
+        for (i, arg) in args.iter().enumerate() {
+            match arg.as_str() {
+                "--input" => {
+                    let path = args
+                        .get(i + 1)
+                        .filter(|path| !path.starts_with("--"))
+                        .ok_or("`--input` expects a path.")?;
+                    sources.push(InputSource::File(path.into()));
+                }
+                "--stdin" => sources.push(InputSource::Stdin),
+                "--example" => {
+                    let number = args.get(i + 1).and_then(|n| n.parse().ok());
+                    sources.push(InputSource::Example(number));
+                }
+                _ => {}
+            }
+        }
+
+        match sources.len() {
+            0 => Ok(InputSource::Puzzle),
+            1 => Ok(sources.remove(0)),
+            _ => Err("Only one of `--input`, `--stdin` and `--example` can be used.".into()),
+        }
+    }
+
+    /// Arguments that select this input source in a solution binary.
+    pub fn to_args(&self) -> Vec<String> {
+        match self {
+            InputSource::Puzzle => vec![],
+            InputSource::Example(None) => vec!["--example".into()],
+            InputSource::Example(Some(n)) => vec!["--example".into(), n.to_string()],
+            InputSource::File(path) => vec!["--input".into(), path.display().to_string()],
+            InputSource::Stdin => vec!["--stdin".into()],
+        }
+    }
+
+    /// Whether this is the real puzzle input, the only input answers can be confirmed or submitted for.
+    pub fn is_puzzle_input(&self) -> bool {
+        *self == InputSource::Puzzle
+    }
+
+    /// Path of the input file in the selected year, [`None`] for stdin. See [`layout`].
+    pub fn path(&self, day: Day) -> Option<PathBuf> {
+        match self {
+            InputSource::Puzzle => Some(layout::data_path(&format!("inputs/{day}.txt"))),
+            InputSource::Example(None | Some(1)) => {
+                Some(layout::data_path(&format!("examples/{day}.txt")))
+            }
+            InputSource::Example(Some(n)) => {
+                Some(layout::data_path(&format!("examples/{day}-{n}.txt")))
+            }
+            InputSource::File(path) => Some(path.clone()),
+            InputSource::Stdin => None,
+        }
+    }
+
+    pub fn read(&self, day: Day) -> io::Result<String> {
+        match self.path(day) {
+            Some(path) => fs::read_to_string(env::current_dir()?.join(path)),
+            None => {
+                let mut input = String::new();
+                io::stdin().read_to_string(&mut input)?;
+                Ok(input)
+            }
+        }
+    }
+
+    /// Describes where the input of `day` is read from.
+    pub fn describe(&self, day: Day) -> String {
+        match self.path(day) {
+            Some(path) => path.display().to_string(),
+            None => "stdin".into(),
+        }
+    }
+}
+
+impl Display for InputSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InputSource::Puzzle => write!(f, "puzzle input"),
+            InputSource::Example(None) => write!(f, "example"),
+            InputSource::Example(Some(n)) => write!(f, "example {n}"),
+            InputSource::File(path) => write!(f, "{}", path.display()),
+            InputSource::Stdin => write!(f, "stdin"),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::InputSource;
+    use crate::{day, template::layout};
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn parses_input_sources() {
+        assert_eq!(
+            InputSource::from_args(&args(&["01", "--time"])),
+            Ok(InputSource::Puzzle)
+        );
+        assert_eq!(
+            InputSource::from_args(&args(&["01", "--example"])),
+            Ok(InputSource::Example(None))
+        );
+        assert_eq!(
+            InputSource::from_args(&args(&["01", "--example", "2", "--time"])),
+            Ok(InputSource::Example(Some(2)))
+        );
+        assert_eq!(
+            InputSource::from_args(&args(&["01", "--input", "stress.txt"])),
+            Ok(InputSource::File("stress.txt".into()))
+        );
+        assert_eq!(
+            InputSource::from_args(&args(&["01", "--stdin"])),
+            Ok(InputSource::Stdin)
+        );
+        assert!(InputSource::from_args(&args(&["01", "--input"])).is_err());
+        assert!(InputSource::from_args(&args(&["01", "--stdin", "--example"])).is_err());
+    }
+
+    #[test]
+    fn roundtrips_args() {
+        for source in [
+            InputSource::Puzzle,
+            InputSource::Example(None),
+            InputSource::Example(Some(3)),
+            InputSource::File("friend.txt".into()),
+            InputSource::Stdin,
+        ] {
+            assert_eq!(InputSource::from_args(&source.to_args()), Ok(source));
+        }
+    }
+
+    #[test]
+    fn resolves_paths() {
+        assert_eq!(
+            InputSource::Puzzle.path(day!(1)),
+            Some(layout::data_path("inputs/01.txt"))
+        );
+        assert_eq!(
+            InputSource::Example(Some(2)).path(day!(12)),
+            Some(layout::data_path("examples/12-2.txt"))
+        );
+        // the first example has no number, same as in `read_example` and `solution!(.., examples: [..])`.
+        assert_eq!(
+            InputSource::Example(Some(1)).path(day!(12)),
+            Some(layout::data_path("examples/12.txt"))
+        );
+        assert_eq!(
+            InputSource::Example(None).path(day!(12)),
+            InputSource::Example(Some(1)).path(day!(12))
+        );
+        assert_eq!(InputSource::Stdin.path(day!(1)), None);
+        assert_eq!(InputSource::Stdin.describe(day!(1)), "stdin");
+    }
+}
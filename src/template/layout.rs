@@ -0,0 +1,196 @@
+//! Where the files of a year live. Solutions are binaries named after their year and day, like `src/bin/2023-05.rs`,
+//! and their data is in a directory per year, like `data/2023/inputs/05.txt`.
+//!
+//! Repositories created before years were supported keep the files of the [configured](Year::configured) year
+//! in `src/bin/05.rs` and `data/inputs/05.txt`. These are used as long as the namespaced file doesn't exist,
+//! `cargo migrate` moves them to the namespaced layout.
+use std::path::{Path, PathBuf};
+use std::{fs, io};
+
+use crate::template::{Day, Year};
+
+static BIN_DIR: &str = "src/bin";
+static DATA_DIR: &str = "data";
+
+/// Data folders of the layout without years, [`legacy_files`] moves everything in them.
+const LEGACY_DATA_FOLDERS: [&str; 5] = ["inputs", "examples", "puzzles", "answers", "submissions"];
+
+/// Directory of the data of `year`, e.g. `data/2023`.
+pub fn data_dir(year: Year) -> PathBuf {
+    Path::new(DATA_DIR).join(year.to_string())
+}
+
+/// Path of a file in the data of the selected year, e.g. `data_path("inputs/05.txt")` is `data/2023/inputs/05.txt`.
+pub fn data_path(relative: &str) -> PathBuf {
+    let year = Year::selected();
+    let namespaced = data_dir(year).join(relative);
+    let legacy = Path::new(DATA_DIR).join(relative);
+
+    if uses_legacy(is_configured(year), namespaced.exists(), legacy.exists()) {
+        legacy
+    } else {
+        namespaced
+    }
+}
+
+/// Name of the solution binary of `day` in the selected year, e.g. `2023-05`.
+pub fn bin_name(day: Day) -> String {
+    let year = Year::selected();
+    let namespaced = format!("{year}-{day}");
+
+    let bin_exists = |name: &str| Path::new(BIN_DIR).join(format!("{name}.rs")).exists();
+    if uses_legacy(
+        is_configured(year),
+        bin_exists(&namespaced),
+        bin_exists(&day.to_string()),
+    ) {
+        day.to_string()
+    } else {
+        namespaced
+    }
+}
+
+/// Path of the solution binary of `day` in the selected year, e.g. `src/bin/2023-05.rs`.
+pub fn bin_path(day: Day) -> PathBuf {
+    Path::new(BIN_DIR).join(format!("{}.rs", bin_name(day)))
+}
+
+/// The year in the name of a solution binary like `2023-05`, or of its test executable like `2023_05-<hash>`.
+pub fn year_of_bin(name: &str) -> Option<Year> {
+    let (year, rest) = name.split_at_checked(4)?;
+    let day = rest.strip_prefix(['-', '_'])?;
+    let (day, rest) = day.split_at_checked(2)?;
+
+    if !day.bytes().all(|b| b.is_ascii_digit()) || !(rest.is_empty() || rest.starts_with('-')) {
+        return None;
+    }
+
+    year.parse().ok()
+}
+
+fn is_configured(year: Year) -> bool {
+    Year::configured() == Some(year)
+}
+
+/// Whether a file is used from the layout without years: only files of the configured year are,
+/// and only until the file exists in the namespaced layout.
+fn uses_legacy(is_configured: bool, namespaced_exists: bool, legacy_exists: bool) -> bool {
+    is_configured && legacy_exists && !namespaced_exists
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// The files of the layout without years, each with the path it moves to in the layout of `year`.
+pub fn legacy_files(year: Year) -> io::Result<Vec<(PathBuf, PathBuf)>> {
+    let mut files = vec![];
+
+    let folders = [PathBuf::from(BIN_DIR)]
+        .into_iter()
+        .chain(LEGACY_DATA_FOLDERS.map(|folder| Path::new(DATA_DIR).join(folder)));
+
+    for folder in folders.filter(|folder| folder.is_dir()) {
+        for entry in fs::read_dir(folder)? {
+            let path = entry?.path();
+            if path.is_file() {
+                files.push(path);
+            }
+        }
+    }
+
+    let timings = Path::new(DATA_DIR).join("timings.json");
+    if timings.is_file() {
+        files.push(timings);
+    }
+
+    let mut moves: Vec<_> = files
+        .into_iter()
+        .filter_map(|from| {
+            let to = namespaced_path(year, &from)?;
+            Some((from, to))
+        })
+        .collect();
+    moves.sort();
+
+    Ok(moves)
+}
+
+/// Where `legacy` moves in the layout of `year`, [`None`] if it isn't part of the layout without years.
+fn namespaced_path(year: Year, legacy: &Path) -> Option<PathBuf> {
+    if let Ok(file) = legacy.strip_prefix(BIN_DIR) {
+        // only days are moved, e.g. `05.rs`.
+        let day = file.to_str()?.strip_suffix(".rs")?;
+        if day.len() != 2 {
+            return None;
+        }
+        let day: Day = day.parse().ok()?;
+        return Some(Path::new(BIN_DIR).join(format!("{year}-{day}.rs")));
+    }
+
+    let relative = legacy.strip_prefix(DATA_DIR).ok()?;
+    let mut components = relative.components();
+    let first = components.next()?.as_os_str().to_str()?;
+
+    let is_legacy = if components.next().is_some() {
+        LEGACY_DATA_FOLDERS.contains(&first)
+    } else {
+        first == "timings.json"
+    };
+
+    is_legacy.then(|| data_dir(year).join(relative))
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{namespaced_path, uses_legacy, year_of_bin};
+    use crate::{template::Year, year};
+    use std::path::{Path, PathBuf};
+
+    #[test]
+    fn prefers_namespaced_files() {
+        assert!(uses_legacy(true, false, true));
+        assert!(!uses_legacy(true, true, true));
+        assert!(!uses_legacy(true, false, false));
+        // files without a year only belong to the configured year.
+        assert!(!uses_legacy(false, false, true));
+    }
+
+    #[test]
+    fn derives_namespaced_paths() {
+        let year = year!(2023);
+        let namespaced = |path: &str| namespaced_path(year, Path::new(path));
+
+        assert_eq!(
+            namespaced("src/bin/05.rs"),
+            Some(PathBuf::from("src/bin/2023-05.rs"))
+        );
+        assert_eq!(
+            namespaced("data/inputs/05.txt"),
+            Some(PathBuf::from("data/2023/inputs/05.txt"))
+        );
+        assert_eq!(
+            namespaced("data/examples/05-2.txt"),
+            Some(PathBuf::from("data/2023/examples/05-2.txt"))
+        );
+        assert_eq!(
+            namespaced("data/timings.json"),
+            Some(PathBuf::from("data/2023/timings.json"))
+        );
+        assert_eq!(namespaced("src/bin/2022-05.rs"), None);
+        assert_eq!(namespaced("src/bin/helpers.rs"), None);
+        assert_eq!(namespaced("data/2022/inputs/05.txt"), None);
+        assert_eq!(namespaced("data/frames/05-part_1.txt"), None);
+    }
+
+    #[test]
+    fn finds_year_of_bin() {
+        assert_eq!(year_of_bin("2023-05"), Year::new(2023));
+        assert_eq!(year_of_bin("2023_05-3f2a9c1d"), Year::new(2023));
+        assert_eq!(year_of_bin("05"), None);
+        assert_eq!(year_of_bin("05-3f2a9c1d"), None);
+        assert_eq!(year_of_bin("advent_of_code"), None);
+        assert_eq!(year_of_bin("solutions-3f2a9c1d"), None);
+        assert_eq!(year_of_bin("2023-5"), None);
+    }
+}
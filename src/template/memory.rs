@@ -0,0 +1,161 @@
+/// A lightweight global allocator that counts the allocations of a solution part, enabled with `--mem`.
+/// Counting is only active while a part is measured, otherwise the allocator forwards to [`System`] directly.
+/// The solution binaries install it through [`solution!`](crate::solution), the main binary with the `registry` feature.
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::hint::black_box;
+use std::sync::atomic::{AtomicBool, AtomicIsize, AtomicUsize, Ordering};
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+/// Heap size relative to the start of the measurement, can become negative if memory allocated before is freed.
+static CURRENT: AtomicIsize = AtomicIsize::new(0);
+static PEAK: AtomicIsize = AtomicIsize::new(0);
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+static BYTES: AtomicUsize = AtomicUsize::new(0);
+
+/// Allocation statistics of a single run of a solution part.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AllocStats {
+    /// Maximum growth of the heap during the run.
+    pub peak_bytes: usize,
+    /// Number of allocations and reallocations.
+    pub allocations: usize,
+    /// Sum of the sizes of all allocations and reallocations.
+    pub bytes: usize,
+}
+
+pub struct CountingAlloc;
+
+impl CountingAlloc {
+    #[inline]
+    fn record(allocated: usize, freed: usize) {
+        #[allow(clippy::cast_possible_wrap)]
+        let current = CURRENT.fetch_add(allocated as isize - freed as isize, Ordering::Relaxed)
+            + allocated as isize
+            - freed as isize;
+
+        if allocated > 0 {
+            ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+            BYTES.fetch_add(allocated, Ordering::Relaxed);
+            PEAK.fetch_max(current, Ordering::Relaxed);
+        }
+    }
+}
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() && ENABLED.load(Ordering::Relaxed) {
+            Self::record(layout.size(), 0);
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() && ENABLED.load(Ordering::Relaxed) {
+            Self::record(layout.size(), 0);
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        if ENABLED.load(Ordering::Relaxed) {
+            Self::record(0, layout.size());
+        }
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() && ENABLED.load(Ordering::Relaxed) {
+            Self::record(new_size, layout.size());
+        }
+        new_ptr
+    }
+}
+
+/// Whether allocations can be counted, which is only the case if [`CountingAlloc`] is the global allocator
+/// of the binary, e.g. not if DHAT is enabled.
+pub fn is_available() -> bool {
+    let (_, stats) = measure(|| black_box(Box::new(0_u8)));
+    stats.allocations > 0
+}
+
+/// Runs `func` and counts the allocations made during the run, on all threads.
+/// Measurements must not be nested.
+pub fn measure<T>(func: impl FnOnce() -> T) -> (T, AllocStats) {
+    CURRENT.store(0, Ordering::Relaxed);
+    PEAK.store(0, Ordering::Relaxed);
+    ALLOCATIONS.store(0, Ordering::Relaxed);
+    BYTES.store(0, Ordering::Relaxed);
+
+    ENABLED.store(true, Ordering::SeqCst);
+    let result = func();
+    ENABLED.store(false, Ordering::SeqCst);
+
+    #[allow(clippy::cast_sign_loss)]
+    let stats = AllocStats {
+        peak_bytes: PEAK.load(Ordering::Relaxed).max(0) as usize,
+        allocations: ALLOCATIONS.load(Ordering::Relaxed),
+        bytes: BYTES.load(Ordering::Relaxed),
+    };
+
+    (result, stats)
+}
+
+/// Formats a number of bytes with a binary prefix, e.g. `1.5 KiB`.
+pub fn format_bytes(bytes: usize) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+
+    format!("{value:.1} {}", UNITS[unit])
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{format_bytes, is_available, measure, CountingAlloc};
+    use std::hint::black_box;
+
+    #[cfg(all(test, not(feature = "dhat-heap")))]
+    #[global_allocator]
+    static GLOBAL: CountingAlloc = CountingAlloc;
+
+    #[test]
+    fn formats_bytes() {
+        assert_eq!(format_bytes(0), "0 B");
+        assert_eq!(format_bytes(1023), "1023 B");
+        assert_eq!(format_bytes(1536), "1.5 KiB");
+        assert_eq!(format_bytes(3 * 1024 * 1024), "3.0 MiB");
+    }
+
+    #[test]
+    fn counts_allocations() {
+        if !is_available() {
+            return;
+        }
+
+        let (sum, stats) = measure(|| {
+            let a = black_box(vec![1u8; 1000]);
+            let b = black_box(vec![2u8; 3000]);
+            drop(a);
+            let c = black_box(vec![3u8; 500]);
+            b.len() + c.len()
+        });
+
+        assert_eq!(sum, 3500);
+        // other tests allocate and free concurrently, so only the lower bounds of the totals are exact.
+        assert!(stats.allocations >= 3);
+        assert!(stats.bytes >= 4500);
+    }
+}
@@ -1,25 +1,52 @@
+use std::fmt::Debug;
 use std::{env, fs};
 
+pub mod answers;
 pub mod aoc_cli;
 pub mod commands;
+pub mod layout;
+pub mod memory;
+pub mod registry;
 pub mod runner;
+pub mod submissions;
+#[cfg(feature = "today")]
+pub mod unlock;
 
+pub use answer::*;
+pub use bench_options::*;
+pub use ctx::*;
 pub use day::*;
+pub use day_set::*;
+pub use input::*;
+pub use solution::*;
+pub use year::*;
 
+#[doc(hidden)]
+pub use paste;
+
+mod answer;
+mod bench_options;
+mod ctx;
 mod day;
+mod day_set;
+mod input;
 mod readme_benchmarks;
+mod report;
 mod run_multi;
+mod scaling;
+mod solution;
 mod timings;
+mod year;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
 pub const ANSI_RESET: &str = "\x1b[0m";
 
-/// Helper function that reads a text file to a string.
+/// Helper function that reads a text file of the selected year to a string, see [`layout`].
 #[must_use]
 pub fn read_file(folder: &str, day: Day) -> String {
     let cwd = env::current_dir().unwrap();
-    let filepath = cwd.join("data").join(folder).join(format!("{day}.txt"));
+    let filepath = cwd.join(layout::data_path(&format!("{folder}/{day}.txt")));
     let f = fs::read_to_string(filepath);
     f.expect("could not open input file")
 }
@@ -28,17 +55,52 @@ pub fn read_file(folder: &str, day: Day) -> String {
 #[must_use]
 pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
     let cwd = env::current_dir().unwrap();
-    let filepath = cwd
-        .join("data")
-        .join(folder)
-        .join(format!("{day}-{part}.txt"));
+    let filepath = cwd.join(layout::data_path(&format!("{folder}/{day}-{part}.txt")));
     let f = fs::read_to_string(filepath);
     f.expect("could not open input file")
 }
 
+/// Helper function that reads an example of `day`, the same file as `--example <example>`: example 1 is
+/// `01.txt`, other examples are e.g. `01-2.txt`.
+#[must_use]
+pub fn read_example(day: Day, example: u8) -> String {
+    InputSource::Example(Some(example))
+        .read(day)
+        .expect("could not open example file")
+}
+
+/// Checks the answer of `part` on `input`, used by the tests generated with `solution!(.., examples: [..])`.
+#[doc(hidden)]
+pub fn check_example<'a, M, P, E>(part: P, input: &'a str, expected: E)
+where
+    P: Part<&'a str, M>,
+    P::Output: PartialEq<E> + Debug,
+    E: Debug,
+{
+    let result = part.call(input, &Ctx::default());
+    assert!(
+        matches!(&result, Some(answer) if *answer == expected),
+        "expected Some({expected:?}), got {result:?}"
+    );
+}
+
 /// Creates the constant `DAY` and sets up the input and runner for each part.
 ///
 /// The optional, second parameter (1 or 2) allows you to only run a single part of the solution.
+/// Alternatively, the second parameter can be a type implementing [`Solution`]. The input is then
+/// parsed once for both parts and the parse time is reported separately.
+///
+/// Alternative implementations of a part can be registered with `variants: [...]`, e.g.
+/// `solution!(6, variants: [part_two_bitgrid, part_two_naive])`. Variants are named after the part
+/// they implement, are checked to produce the same answer and their timings are compared.
+///
+/// Expected answers on the examples can be declared with `examples: [...]`, each as
+/// `(example, part, answer)`, e.g. `solution!(8, examples: [(1, part_one, 14), (2, part_two, 9)])`.
+/// Every expectation becomes a test named like `part_two_example_2`, see [`read_example`].
+/// They can be combined with variants: `solution!(1, variants: [..], examples: [..])`, or with a
+/// [`Solution`], which parses each example before running the part: `solution!(7, Day07, examples: [..])`.
+///
+/// Besides `main`, the macro defines `run_parts(input)`, which runs the day in-process, see [`registry`].
 #[macro_export]
 macro_rules! solution {
     ($day:expr) => {
@@ -50,8 +112,135 @@ macro_rules! solution {
     ($day:expr, 2) => {
         $crate::solution!(@impl $day, [part_two, 2]);
     };
+    ($day:expr, examples: [$($example:tt),* $(,)?]) => {
+        $crate::solution!(@impl $day, [part_one, 1] [part_two, 2]);
+        $crate::solution!(@examples $($example)*);
+    };
+    ($day:expr, variants: [$($variant:ident),* $(,)?] $(, examples: [$($example:tt),* $(,)?])?) => {
+        $( $crate::solution!(@examples $($example)*); )?
+        $crate::solution!(@common $day);
+
+        /// Runs the parts and their variants on `input`.
+        pub fn run_parts(input: &str) {
+            use $crate::template::runner::*;
+            let part_1 = run_part(part_one, input, DAY, 1);
+            let part_2 = run_part(part_two, input, DAY, 2);
+
+            let mut variants = Variants::new(DAY, part_1, part_2);
+            $( variants.run(stringify!($variant), $variant, input); )*
+            variants.finish();
+        }
+
+        /// Passes the parts and their variants to `bench`, see [`Registered`]($crate::template::registry::Registered).
+        pub fn bench_phases(bench: &mut $crate::template::registry::Bench) {
+            $crate::solution!(@bench bench, [part_one, "part_1"] [part_two, "part_2"] $( [$variant, stringify!($variant)] )*);
+        }
+    };
+    ($day:expr, $solution:ty, examples: [$($example:tt),* $(,)?]) => {
+        $crate::solution!($day, $solution);
+        $crate::solution!(@solution_examples $solution, $($example)*);
+    };
+    ($day:expr, $solution:ty) => {
+        $crate::solution!(@common $day);
+
+        /// Parses `input` once and runs both parts on it.
+        pub fn run_parts(input: &str) {
+            $crate::template::runner::run_solution::<$solution>(input, DAY);
+        }
+
+        /// Passes parsing and both parts to `bench`, the parts run on the input parsed in advance.
+        pub fn bench_phases(bench: &mut $crate::template::registry::Bench) {
+            use $crate::template::Solution;
+            use std::hint::black_box;
+
+            bench("parse", &|input| Box::new(move || {
+                black_box(<$solution>::parse(black_box(input)));
+                true
+            }));
+            bench("part_1", &|input| {
+                let parsed = <$solution>::parse(input);
+                Box::new(move || {
+                    black_box(<$solution>::part_one(black_box(&parsed))).is_some()
+                })
+            });
+            bench("part_2", &|input| {
+                let parsed = <$solution>::parse(input);
+                Box::new(move || {
+                    black_box(<$solution>::part_two(black_box(&parsed))).is_some()
+                })
+            });
+        }
+    };
 
     (@impl $day:expr, $( [$func:expr, $part:expr] )*) => {
+        $crate::solution!(@common $day);
+
+        /// Runs the parts on `input`.
+        pub fn run_parts(input: &str) {
+            use $crate::template::runner::*;
+            $( run_part($func, input, DAY, $part); )*
+        }
+
+        /// Passes the parts to `bench`, see [`Registered`]($crate::template::registry::Registered).
+        pub fn bench_phases(bench: &mut $crate::template::registry::Bench) {
+            $crate::solution!(@bench bench, $( [$func, concat!("part_", $part)] )*);
+        }
+    };
+
+    (@bench $bench:ident, $( [$func:expr, $name:expr] )*) => {
+        $(
+            $bench($name, &|input| Box::new(move || {
+                std::hint::black_box($crate::template::registry::call_part(
+                    &$func,
+                    std::hint::black_box(input),
+                ))
+                .is_some()
+            }));
+        )*
+    };
+
+    (@examples) => {};
+    (@examples $(($example:literal, $part:ident, $expected:expr))*) => {
+        #[cfg(test)]
+        mod examples {
+            use super::*;
+
+            $(
+                $crate::template::paste::paste! {
+                    #[test]
+                    fn [<$part _example_ $example>]() {
+                        let input = $crate::template::read_example(DAY, $example);
+                        $crate::template::check_example($part, &input, $expected);
+                    }
+                }
+            )*
+        }
+    };
+
+    (@solution_examples $solution:ty,) => {};
+    (@solution_examples $solution:ty, $(($example:literal, $part:ident, $expected:expr))*) => {
+        #[cfg(test)]
+        mod examples {
+            use super::*;
+            use $crate::template::Solution;
+
+            $(
+                $crate::template::paste::paste! {
+                    #[test]
+                    fn [<$part _example_ $example>]() {
+                        let input = $crate::template::read_example(DAY, $example);
+                        $crate::template::check_example(
+                            |input: &str| <$solution>::$part(&<$solution>::parse(input)),
+                            &input,
+                            $expected,
+                        );
+                    }
+                }
+            )*
+        }
+    };
+
+    (@common $day:expr) => {
         /// The current day.
         const DAY: $crate::template::Day = $crate::day!($day);
 
@@ -59,10 +248,17 @@ macro_rules! solution {
         #[global_allocator]
         static ALLOC: dhat::Alloc = dhat::Alloc;
 
+        // NOTE: counts the allocations with `--mem`. DHAT brings its own global allocator, and with the
+        // registry, the days are modules of the main binary, which installs the allocator itself.
+        #[cfg(not(any(feature = "dhat-heap", feature = "registry")))]
+        #[global_allocator]
+        static ALLOC: $crate::template::memory::CountingAlloc = $crate::template::memory::CountingAlloc;
+
         fn main() {
             use $crate::template::runner::*;
-            let input = $crate::template::read_file("inputs", DAY);
-            $( run_part($func, &input, DAY, $part); )*
+            let input = read_input(DAY);
+            run_parts(&input);
+            finish();
         }
     };
 }
@@ -2,10 +2,14 @@
 /// The approach taken is similar to how `aoc-readme-stars` handles this.
 use std::{fs, io};
 
+use crate::template::memory::format_bytes;
 use crate::template::timings::Timings;
-use crate::template::Day;
+use crate::template::{layout, BenchOptions, Day, Year};
 
+/// Marks the table of a readme written before years were supported.
 static MARKER: &str = "<!--- benchmarking table --->";
+/// Every marker starts with this, e.g. `<!--- benchmarking table 2023 --->`.
+static MARKER_PREFIX: &str = "<!--- benchmarking table";
 
 #[allow(dead_code)]
 #[derive(Debug)]
@@ -25,13 +29,36 @@ pub struct TablePosition {
     pos_end: usize,
 }
 
+/// Marker and title of the benchmarking table of a year.
+struct Table {
+    marker: String,
+    title: String,
+}
+
+impl Table {
+    fn of_year(year: Year) -> Self {
+        Table {
+            marker: format!("<!--- benchmarking table {year} --->"),
+            title: format!("{year} Benchmarks"),
+        }
+    }
+
+    /// The single table of a readme written before years were supported.
+    fn legacy() -> Self {
+        Table {
+            marker: MARKER.into(),
+            title: "Benchmarks".into(),
+        }
+    }
+}
+
 #[must_use]
 pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
+    format!("./{}", layout::bin_path(day).display())
 }
 
-fn locate_table(readme: &str) -> Result<TablePosition, Error> {
-    let matches: Vec<_> = readme.match_indices(MARKER).collect();
+fn locate_table(readme: &str, marker: &str) -> Result<TablePosition, Error> {
+    let matches: Vec<_> = readme.match_indices(marker).collect();
 
     if matches.len() > 2 {
         return Err(Error::Parser(
@@ -52,76 +79,265 @@ fn locate_table(readme: &str) -> Result<TablePosition, Error> {
     Ok(TablePosition { pos_start, pos_end })
 }
 
-fn construct_table(prefix: &str, timings: Timings, total_millis: f64) -> String {
-    let header = format!("{prefix} Benchmarks");
+/// Describes the settings the days were benched with. Days benched with other settings than the rest are listed.
+fn describe_bench_settings(timings: &Timings) -> Option<String> {
+    let mut groups: Vec<(BenchOptions, Vec<String>)> = vec![];
 
-    let mut lines: Vec<String> = vec![
-        MARKER.into(),
-        header,
-        String::new(),
-        "| Day | Part 1 | Part 2 |".into(),
-        "| :---: | :---: | :---:  |".into(),
-    ];
+    for timing in &timings.data {
+        let Some(bench) = timing.bench else {
+            continue;
+        };
+
+        let day = timing.day.into_inner().to_string();
+        match groups.iter_mut().find(|(options, _)| *options == bench) {
+            Some((_, days)) => days.push(day),
+            None => groups.push((bench, vec![day])),
+        }
+    }
+
+    match groups.as_slice() {
+        [] => None,
+        [(options, _)] => Some(options.to_string()),
+        groups => Some(
+            groups
+                .iter()
+                .map(|(options, days)| {
+                    let label = if days.len() == 1 { "day" } else { "days" };
+                    format!("{options} ({label} {})", days.join(", "))
+                })
+                .collect::<Vec<_>>()
+                .join("; "),
+        ),
+    }
+}
+
+fn construct_table(
+    prefix: &str,
+    table: &Table,
+    timings: Timings,
+    total_millis: f64,
+    bin_path: &dyn Fn(Day) -> String,
+) -> String {
+    let header = format!("{prefix} {}", table.title);
+    // only show the parse column once a solution reports parse times separately.
+    let has_parse = timings.data.iter().any(|t| t.parse.is_some());
+    // same for the memory column, which needs timings benched with `--mem`.
+    let has_memory = timings.data.iter().any(|t| t.peak_bytes().is_some());
+
+    let bench_settings = describe_bench_settings(&timings);
+
+    let mut lines: Vec<String> = vec![table.marker.clone(), header, String::new()];
+
+    let mut columns = vec!["Day"];
+    if has_parse {
+        columns.push("Parse");
+    }
+    columns.extend(["Part 1", "Part 2"]);
+    if has_memory {
+        columns.push("Peak memory");
+    }
+
+    lines.push(format!("| {} |", columns.join(" | ")));
+    lines.push(format!("|{}  |", vec![" :---:"; columns.len()].join(" |")));
 
     for timing in timings.data {
-        let path = get_path_for_bin(timing.day);
+        let path = bin_path(timing.day);
+        let parse = if has_parse {
+            format!(
+                " `{}` |",
+                timing.parse.clone().unwrap_or_else(|| "-".into())
+            )
+        } else {
+            String::new()
+        };
+        let memory = if has_memory {
+            format!(
+                " `{}` |",
+                timing.peak_bytes().map_or_else(|| "-".into(), format_bytes)
+            )
+        } else {
+            String::new()
+        };
+
         lines.push(format!(
-            "| [Day {}]({}) | `{}` | `{}` |",
+            "| [Day {}]({}) |{} `{}` | `{}` |{}",
             timing.day.into_inner(),
             path,
+            parse,
             timing.part_1.unwrap_or_else(|| "-".into()),
-            timing.part_2.unwrap_or_else(|| "-".into())
+            timing.part_2.unwrap_or_else(|| "-".into()),
+            memory
         ));
     }
 
     lines.push(String::new());
     lines.push(format!("**Total: {total_millis:.2}ms**"));
-    lines.push(MARKER.into());
+    if let Some(settings) = bench_settings {
+        lines.push(String::new());
+        lines.push(format!("_Benched with {settings}._"));
+    }
+    lines.push(table.marker.clone());
 
     lines.join("\n")
 }
 
-fn update_content(s: &mut String, timings: Timings, total_millis: f64) -> Result<(), Error> {
-    let positions = locate_table(s)?;
-    let table = construct_table("##", timings, total_millis);
-    s.replace_range(positions.pos_start..positions.pos_end, &table);
+/// Replaces the table of `year`. The configured year may use the table of a readme written before years were supported.
+/// A year without a table gets a new one after the last table.
+fn update_content(
+    s: &mut String,
+    year: Year,
+    is_configured: bool,
+    timings: Timings,
+    total_millis: f64,
+    bin_path: &dyn Fn(Day) -> String,
+) -> Result<(), Error> {
+    let mut table = Table::of_year(year);
+    if !s.contains(&table.marker) && is_configured && s.contains(MARKER) {
+        table = Table::legacy();
+    }
+
+    if s.contains(&table.marker) {
+        let positions = locate_table(s, &table.marker)?;
+        let content = construct_table("##", &table, timings, total_millis, bin_path);
+        s.replace_range(positions.pos_start..positions.pos_end, &content);
+        return Ok(());
+    }
+
+    let pos_end = s
+        .rfind(MARKER_PREFIX)
+        .and_then(|pos| s[pos..].find("--->").map(|end| pos + end + "--->".len()))
+        .ok_or_else(|| Error::Parser("Could not find table start position.".into()))?;
+
+    let content = construct_table("##", &table, timings, total_millis, bin_path);
+    s.insert_str(pos_end, &format!("\n\n{content}"));
     Ok(())
 }
 
+/// Marks the table of a readme written before years were supported as the table of `year`.
+/// Returns whether there was such a table.
+fn migrate_content(s: &mut String, year: Year) -> Result<bool, Error> {
+    if !s.contains(MARKER) {
+        return Ok(false);
+    }
+
+    let legacy = Table::legacy();
+    let table = Table::of_year(year);
+
+    let positions = locate_table(s, MARKER)?;
+    let content = s[positions.pos_start..positions.pos_end]
+        .replace(MARKER, &table.marker)
+        .replacen(
+            &format!("## {}\n", legacy.title),
+            &format!("## {}\n", table.title),
+            1,
+        );
+    s.replace_range(positions.pos_start..positions.pos_end, &content);
+    Ok(true)
+}
+
 pub fn update(timings: Timings) -> Result<(), Error> {
     let path = "README.md";
+    let year = Year::selected();
     let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
     let total_millis = timings.total_millis();
-    update_content(&mut readme, timings, total_millis)?;
+    update_content(
+        &mut readme,
+        year,
+        Year::configured() == Some(year),
+        timings,
+        total_millis,
+        &get_path_for_bin,
+    )?;
     fs::write(path, &readme)?;
     Ok(())
 }
 
+/// Marks the table of a readme written before years were supported as the table of `year`, see [`layout`].
+/// Returns whether there was such a table.
+pub fn migrate(year: Year) -> Result<bool, Error> {
+    let path = "README.md";
+    let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
+    if !migrate_content(&mut readme, year)? {
+        return Ok(false);
+    }
+    fs::write(path, &readme)?;
+    Ok(true)
+}
+
 #[cfg(feature = "test_lib")]
 mod tests {
-    use super::{update_content, MARKER};
-    use crate::{day, template::timings::Timing, template::timings::Timings};
+    use super::{migrate_content, update_content, Error, MARKER};
+    use crate::{
+        day,
+        template::memory::AllocStats,
+        template::timings::{Timing, Timings},
+        template::{BenchOptions, Day, Year},
+        year,
+    };
+    use std::time::Duration;
+
+    fn bin_path(day: Day) -> String {
+        format!("./src/bin/{day}.rs")
+    }
+
+    /// Updates the table of the configured year 2024, which may be a table without year.
+    fn update_legacy(s: &mut String, timings: Timings, total_millis: f64) -> Result<(), Error> {
+        update_content(s, year!(2024), true, timings, total_millis, &bin_path)
+    }
+
+    fn update_year(s: &mut String, year: Year, timings: Timings) -> Result<(), Error> {
+        update_content(s, year, false, timings, 190.0, &|day| {
+            format!("./src/bin/{year}-{day}.rs")
+        })
+    }
 
     fn get_mock_timings() -> Timings {
         Timings {
             data: vec![
                 Timing {
                     day: day!(1),
+                    parse: None,
                     part_1: Some("10ms".into()),
                     part_2: Some("20ms".into()),
                     total_nanos: 3e+10,
+                    parse_memory: None,
+                    part_1_memory: None,
+                    part_2_memory: None,
+                    parse_stats: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    bench: None,
+                    threads: None,
                 },
                 Timing {
                     day: day!(2),
+                    parse: None,
                     part_1: Some("30ms".into()),
                     part_2: Some("40ms".into()),
                     total_nanos: 7e+10,
+                    parse_memory: None,
+                    part_1_memory: None,
+                    part_2_memory: None,
+                    parse_stats: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    bench: None,
+                    threads: None,
                 },
                 Timing {
                     day: day!(4),
+                    parse: None,
                     part_1: Some("40ms".into()),
                     part_2: Some("50ms".into()),
                     total_nanos: 9e+10,
+                    parse_memory: None,
+                    part_1_memory: None,
+                    part_2_memory: None,
+                    parse_stats: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    bench: None,
+                    threads: None,
                 },
             ],
         }
@@ -131,28 +347,28 @@ mod tests {
     #[should_panic]
     fn errors_if_marker_not_present() {
         let mut s = "# readme".to_string();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_legacy(&mut s, get_mock_timings(), 190.0).unwrap();
     }
 
     #[test]
     #[should_panic]
     fn errors_if_too_many_markers_present() {
         let mut s = format!("{} {} {}", MARKER, MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_legacy(&mut s, get_mock_timings(), 190.0).unwrap();
     }
 
     #[test]
     fn updates_empty_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_legacy(&mut s, get_mock_timings(), 190.0).unwrap();
         assert_eq!(s.contains("## Benchmarks"), true);
     }
 
     #[test]
     fn updates_existing_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_legacy(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_legacy(&mut s, get_mock_timings(), 190.0).unwrap();
         assert_eq!(s.matches(MARKER).collect::<Vec<&str>>().len(), 2);
         assert_eq!(s.matches("## Benchmarks").collect::<Vec<&str>>().len(), 1);
     }
@@ -160,7 +376,7 @@ mod tests {
     #[test]
     fn format_benchmarks() {
         let mut s = format!("foo\nbar\n{}\n{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_legacy(&mut s, get_mock_timings(), 190.0).unwrap();
         let expected = [
             "foo",
             "bar",
@@ -180,4 +396,120 @@ mod tests {
         .join("\n");
         assert_eq!(s, expected);
     }
+
+    #[test]
+    fn format_benchmarks_with_parse() {
+        let mut timings = get_mock_timings();
+        timings.data[1].parse = Some("5µs".into());
+
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        update_legacy(&mut s, timings, 190.0).unwrap();
+        let expected = [
+            "<!--- benchmarking table --->",
+            "## Benchmarks",
+            "",
+            "| Day | Parse | Part 1 | Part 2 |",
+            "| :---: | :---: | :---: | :---:  |",
+            "| [Day 1](./src/bin/01.rs) | `-` | `10ms` | `20ms` |",
+            "| [Day 2](./src/bin/02.rs) | `5µs` | `30ms` | `40ms` |",
+            "| [Day 4](./src/bin/04.rs) | `-` | `40ms` | `50ms` |",
+            "",
+            "**Total: 190.00ms**",
+            "<!--- benchmarking table --->",
+        ]
+        .join("\n");
+        assert_eq!(s, expected);
+    }
+
+    #[test]
+    fn format_benchmarks_with_memory() {
+        let mut timings = get_mock_timings();
+        timings.data[0].part_2_memory = Some(AllocStats {
+            peak_bytes: 1536,
+            allocations: 3,
+            bytes: 2048,
+        });
+
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        update_legacy(&mut s, timings, 190.0).unwrap();
+        let expected = [
+            "<!--- benchmarking table --->",
+            "## Benchmarks",
+            "",
+            "| Day | Part 1 | Part 2 | Peak memory |",
+            "| :---: | :---: | :---: | :---:  |",
+            "| [Day 1](./src/bin/01.rs) | `10ms` | `20ms` | `1.5 KiB` |",
+            "| [Day 2](./src/bin/02.rs) | `30ms` | `40ms` | `-` |",
+            "| [Day 4](./src/bin/04.rs) | `40ms` | `50ms` | `-` |",
+            "",
+            "**Total: 190.00ms**",
+            "<!--- benchmarking table --->",
+        ]
+        .join("\n");
+        assert_eq!(s, expected);
+    }
+
+    #[test]
+    fn format_benchmarks_with_bench_settings() {
+        let mut timings = get_mock_timings();
+        for timing in &mut timings.data {
+            timing.bench = Some(BenchOptions::default());
+        }
+
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        update_legacy(&mut s, timings.clone(), 190.0).unwrap();
+        assert!(s.ends_with(
+            "**Total: 190.00ms**\n\n_Benched with 1s per part, 10-10000 samples, 10% warmup._\n<!--- benchmarking table --->"
+        ));
+
+        timings.data[1].bench = Some(BenchOptions {
+            time: Duration::from_secs(5),
+            ..BenchOptions::default()
+        });
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        update_legacy(&mut s, timings, 190.0).unwrap();
+        assert!(s.contains(
+            "_Benched with 1s per part, 10-10000 samples, 10% warmup (days 1, 4); 5s per part, 10-10000 samples, 10% warmup (day 2)._"
+        ));
+    }
+
+    #[test]
+    fn updates_tables_per_year() {
+        let mut s = format!("foo\n{}\n{}\nbaz", MARKER, MARKER);
+        update_year(&mut s, year!(2023), get_mock_timings()).unwrap();
+        update_legacy(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_year(&mut s, year!(2023), get_mock_timings()).unwrap();
+
+        // the table without year stays the table of the configured year.
+        assert_eq!(s.matches(MARKER).count(), 2);
+        assert_eq!(s.matches("<!--- benchmarking table 2023 --->").count(), 2);
+        assert_eq!(s.matches("## 2023 Benchmarks").count(), 1);
+        assert!(s.contains("| [Day 1](./src/bin/2023-01.rs) | `10ms` | `20ms` |"));
+        assert!(s.find("## Benchmarks") < s.find("## 2023 Benchmarks"));
+        assert!(s.ends_with("**Total: 190.00ms**\n<!--- benchmarking table 2023 --->\nbaz"));
+    }
+
+    #[test]
+    fn migrates_table_without_year() {
+        let mut s = format!("foo\n{}\n{}\nbaz", MARKER, MARKER);
+        update_legacy(&mut s, get_mock_timings(), 190.0).unwrap();
+
+        assert!(migrate_content(&mut s, year!(2024)).unwrap());
+        assert!(!s.contains(MARKER));
+        assert!(s.starts_with("foo\n<!--- benchmarking table 2024 --->\n## 2024 Benchmarks\n"));
+        assert!(!migrate_content(&mut s, year!(2024)).unwrap());
+
+        // from now on, the table is found by its year.
+        update_content(
+            &mut s,
+            year!(2024),
+            true,
+            get_mock_timings(),
+            190.0,
+            &bin_path,
+        )
+        .unwrap();
+        assert_eq!(s.matches("## 2024 Benchmarks").count(), 1);
+        assert!(!s.contains(MARKER));
+    }
 }
@@ -0,0 +1,76 @@
+//! Solutions compiled into the main binary with the `registry` feature, see `build.rs`.
+//! `all` and `time` run registered days in-process instead of spawning a solution binary per day,
+//! and the `cargo bench` harness in `benches/` benches the phases of every registered day.
+use std::sync::OnceLock;
+
+use crate::template::run_multi::child_commands::ChildOutput;
+use crate::template::{runner, Ctx, Day, InputSource, Part, Year};
+
+/// Prepares a phase of a solution for benching: untimed work like parsing for the parts happens in the call,
+/// the returned routine runs the timed phase on the input once and returns whether it produced an answer.
+pub type Prepare = dyn for<'a> Fn(&'a str) -> Box<dyn Fn() -> bool + 'a>;
+
+/// Receives the phases of a solution as `bench(name, prepare)`, see [`Registered::bench_phases`].
+pub type Bench<'a> = dyn FnMut(&str, &Prepare) + 'a;
+
+/// A solution compiled into the main binary.
+#[derive(Clone, Copy)]
+pub struct Registered {
+    pub year: Year,
+    pub day: Day,
+    /// Runs the parts on the input, the `run_parts` function generated by [`solution!`](crate::solution).
+    pub run_parts: fn(&str),
+    /// Calls `bench(name, prepare)` for each phase, the `bench_phases` function generated by [`solution!`](crate::solution).
+    /// Phases are named like the reports, e.g. `parse`, `part_1` or the name of a variant.
+    pub bench_phases: fn(bench: &mut Bench<'_>),
+}
+
+/// Calls a part the way it is benched: with an inactive [`Ctx`], so instrumentation doesn't distort the timing.
+#[doc(hidden)]
+pub fn call_part<'a, M, P: Part<&'a str, M>>(part: &P, input: &'a str) -> Option<P::Output> {
+    part.call(input, &Ctx::default())
+}
+
+static REGISTRY: OnceLock<&'static [Registered]> = OnceLock::new();
+
+/// Registers the solutions compiled into the main binary. Only the first call has an effect.
+pub fn register(solutions: &'static [Registered]) {
+    let _ = REGISTRY.set(solutions);
+}
+
+/// The registered solution of `day` in `year`, if any.
+pub fn find(year: Year, day: Day) -> Option<&'static Registered> {
+    REGISTRY
+        .get()?
+        .iter()
+        .find(|solution| solution.year == year && solution.day == day)
+}
+
+/// Runs `solution` on the puzzle input in this process and collects its reports,
+/// like [`run_solution`](super::run_multi::child_commands::run_solution) does for a solution binary.
+pub fn run_solution(solution: &Registered) -> ChildOutput {
+    let day = solution.day;
+
+    let input = match InputSource::Puzzle.read(day) {
+        Ok(input) => input,
+        Err(e) => {
+            eprintln!(
+                "Could not read input from {}: {e}",
+                InputSource::Puzzle.describe(day)
+            );
+            return ChildOutput {
+                reports: vec![],
+                timed_out: false,
+                failed: true,
+            };
+        }
+    };
+
+    (solution.run_parts)(&input);
+
+    ChildOutput {
+        reports: runner::take_reports(),
+        timed_out: false,
+        failed: runner::take_failed(),
+    }
+}
@@ -0,0 +1,317 @@
+/// Machine-readable results emitted by the solution binaries with `--format json`.
+/// Each timed phase of a solution (parsing or a part) is reported as one JSON object per line.
+use std::{collections::HashMap, str::FromStr, time::Duration};
+use tinyjson::JsonValue;
+
+use crate::template::{memory::AllocStats, runner::BenchStats, Day};
+
+/// The result of one timed phase of a solution.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Report {
+    pub day: Day,
+    /// `parse`, `part_1` or `part_2`, matching the keys in `timings.json`.
+    pub name: String,
+    pub answer: Option<String>,
+    pub stats: BenchStats,
+    /// Allocations of the first run, if counted with `--mem`.
+    pub memory: Option<AllocStats>,
+    /// Message and location of the panic, if the phase panicked.
+    pub error: Option<String>,
+}
+
+impl Report {
+    pub fn to_json_line(&self) -> String {
+        JsonValue::from(self)
+            .stringify()
+            .expect("report should be serializable")
+    }
+
+    /// Parses a line of solution output, returns [`None`] for anything that isn't a report.
+    pub fn from_json_line(line: &str) -> Option<Self> {
+        if !line.starts_with('{') {
+            return None;
+        }
+
+        let json = JsonValue::from_str(line).ok()?;
+        Report::try_from(&json).ok()
+    }
+
+    /// Duration the report is aggregated by: the mean of all samples.
+    pub fn nanos(&self) -> f64 {
+        self.stats.mean.as_nanos() as f64
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[allow(clippy::cast_precision_loss)]
+fn nanos_value(duration: Duration) -> JsonValue {
+    JsonValue::Number(duration.as_nanos() as f64)
+}
+
+impl From<&Report> for JsonValue {
+    fn from(value: &Report) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        map.insert("name".into(), JsonValue::String(value.name.clone()));
+        map.insert(
+            "answer".into(),
+            match &value.answer {
+                Some(answer) => JsonValue::String(answer.clone()),
+                None => JsonValue::Null,
+            },
+        );
+        map.insert(
+            "error".into(),
+            match &value.error {
+                Some(error) => JsonValue::String(error.clone()),
+                None => JsonValue::Null,
+            },
+        );
+        map.insert("nanos".into(), nanos_value(value.stats.mean));
+        #[allow(clippy::cast_precision_loss)]
+        map.insert(
+            "samples".into(),
+            JsonValue::Number(value.stats.samples as f64),
+        );
+
+        map.insert("stats".into(), JsonValue::from(&value.stats));
+        map.insert(
+            "memory".into(),
+            match &value.memory {
+                Some(memory) => JsonValue::from(memory),
+                None => JsonValue::Null,
+            },
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Report {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected report to be a JSON object.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected report.day to be a Day struct.")?;
+
+        let name = json
+            .get("name")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected report.name to be a string.")?;
+
+        let answer = json
+            .get("answer")
+            .map(|v| if v.is_null() { None } else { v.get::<String>() })
+            .ok_or("Expected report.answer to be null or string.")?;
+
+        let error = json.get("error").and_then(|v| v.get::<String>()).cloned();
+
+        let stats = json
+            .get("stats")
+            .ok_or("Expected report.stats to be an object.")?;
+
+        Ok(Report {
+            day,
+            name: name.clone(),
+            answer: answer.cloned(),
+            stats: BenchStats::try_from(stats)?,
+            memory: json
+                .get("memory")
+                .filter(|v| !v.is_null())
+                .map(AllocStats::try_from)
+                .transpose()?,
+            error,
+        })
+    }
+}
+
+/// The summary statistics, and the recorded samples if they were kept, see [`BenchStats::recorded`].
+impl From<&BenchStats> for JsonValue {
+    fn from(value: &BenchStats) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+        #[allow(clippy::cast_precision_loss)]
+        map.insert("samples".into(), JsonValue::Number(value.samples as f64));
+        #[allow(clippy::cast_precision_loss)]
+        map.insert("outliers".into(), JsonValue::Number(value.outliers as f64));
+        map.insert("min".into(), nanos_value(value.min));
+        map.insert("median".into(), nanos_value(value.median));
+        map.insert("mean".into(), nanos_value(value.mean));
+        map.insert("p95".into(), nanos_value(value.p95));
+        map.insert("std_dev".into(), nanos_value(value.std_dev));
+
+        if !value.recorded.is_empty() {
+            map.insert(
+                "recorded".into(),
+                JsonValue::Array(value.recorded.iter().copied().map(nanos_value).collect()),
+            );
+        }
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for BenchStats {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected stats to be a JSON object.")?;
+
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>().copied())
+                .ok_or(format!("Expected stats.{key} to be a number."))
+        };
+        let duration = |key: &str| number(key).map(|n| Duration::from_nanos(n as u64));
+
+        // NOTE: optional, the samples are only kept with `--samples`.
+        let recorded = match json.get("recorded") {
+            Some(recorded) => recorded
+                .get::<Vec<JsonValue>>()
+                .and_then(|samples| {
+                    samples
+                        .iter()
+                        .map(|v| v.get::<f64>().map(|n| Duration::from_nanos(*n as u64)))
+                        .collect()
+                })
+                .ok_or("Expected stats.recorded to be an array of numbers.")?,
+            None => vec![],
+        };
+
+        Ok(BenchStats {
+            samples: number("samples")? as usize,
+            outliers: number("outliers")? as usize,
+            min: duration("min")?,
+            median: duration("median")?,
+            mean: duration("mean")?,
+            p95: duration("p95")?,
+            std_dev: duration("std_dev")?,
+            recorded,
+        })
+    }
+}
+
+#[allow(clippy::cast_precision_loss)]
+impl From<&AllocStats> for JsonValue {
+    fn from(value: &AllocStats) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+        map.insert(
+            "peak_bytes".into(),
+            JsonValue::Number(value.peak_bytes as f64),
+        );
+        map.insert(
+            "allocations".into(),
+            JsonValue::Number(value.allocations as f64),
+        );
+        map.insert("bytes".into(), JsonValue::Number(value.bytes as f64));
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for AllocStats {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected memory to be a JSON object.")?;
+
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>().copied())
+                .map(|n| n as usize)
+                .ok_or(format!("Expected memory.{key} to be a number."))
+        };
+
+        Ok(AllocStats {
+            peak_bytes: number("peak_bytes")?,
+            allocations: number("allocations")?,
+            bytes: number("bytes")?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Report;
+    use crate::{
+        day,
+        template::{memory::AllocStats, runner::BenchStats},
+    };
+    use std::time::Duration;
+
+    #[test]
+    fn roundtrips_reports() {
+        let report = Report {
+            day: day!(3),
+            name: "part_1".into(),
+            answer: Some("Part 1: \"@ (2s @ 5 samples)\"\nline".into()),
+            stats: BenchStats {
+                samples: 100,
+                outliers: 2,
+                min: Duration::from_nanos(10),
+                median: Duration::from_nanos(20),
+                mean: Duration::from_nanos(25),
+                p95: Duration::from_nanos(40),
+                std_dev: Duration::from_nanos(5),
+                recorded: vec![Duration::from_nanos(10), Duration::from_nanos(40)],
+            },
+            memory: Some(AllocStats {
+                peak_bytes: 4096,
+                allocations: 12,
+                bytes: 10_000,
+            }),
+            error: None,
+        };
+
+        let line = report.to_json_line();
+        assert!(!line.contains('\n'));
+        assert_eq!(Report::from_json_line(&line), Some(report.clone()));
+
+        let failed = Report {
+            answer: None,
+            memory: None,
+            error: Some("invalid input @ src/bin/07.rs:12:5".into()),
+            ..report
+        };
+        assert_eq!(Report::from_json_line(&failed.to_json_line()), Some(failed));
+    }
+
+    #[test]
+    fn omits_samples_unless_kept() {
+        let report = Report {
+            day: day!(3),
+            name: "part_1".into(),
+            answer: Some("42".into()),
+            stats: BenchStats::single(Duration::from_nanos(10)).summary(),
+            memory: None,
+            error: None,
+        };
+
+        let line = report.to_json_line();
+        assert!(!line.contains("recorded"));
+        assert_eq!(Report::from_json_line(&line), Some(report));
+    }
+
+    #[test]
+    fn ignores_other_output() {
+        assert_eq!(Report::from_json_line("Part 1: 42 (10ns)"), None);
+        assert_eq!(Report::from_json_line("{ not json"), None);
+        assert_eq!(Report::from_json_line(r#"{ "day": "01" }"#), None);
+    }
+}
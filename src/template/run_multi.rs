@@ -1,14 +1,92 @@
-use std::{collections::HashSet, io};
+use std::{collections::HashSet, io, time::Duration};
 
-use crate::template::{Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+use crate::template::{BenchOptions, Day, Year, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
 
 use super::{
     all_days,
+    answers::{AnswerCheck, Answers},
+    registry::{self, Registered},
+    report::Report,
+    runner,
     timings::{Timing, Timings},
 };
 
-pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -> Option<Timings> {
+/// An answer that differs from the confirmed answer in `data/<year>/answers`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Drift {
+    pub day: Day,
+    pub part: u8,
+    pub expected: String,
+    pub actual: String,
+}
+
+/// The outcome of running the solutions of several days.
+pub struct RunSummary {
+    /// Timings of the days that ran successfully, if timed.
+    pub timings: Option<Timings>,
+    /// Whether any day timed out, panicked or exited with an error.
+    pub failed: bool,
+}
+
+/// Describes every phase of `day` that panicked.
+pub fn find_panics(reports: &[Report], day: Day) -> Vec<String> {
+    reports
+        .iter()
+        .filter(|report| report.day == day)
+        .filter_map(|report| {
+            let error = report.error.as_ref()?;
+            let phase = match report.name.as_str() {
+                "part_1" => "part 1",
+                "part_2" => "part 2",
+                name => name,
+            };
+            Some(format!("💥 Day {day} {phase}: {error}"))
+        })
+        .collect()
+}
+
+/// Compares the answers of `reports` with the confirmed answers of `day`.
+pub fn find_drift(reports: &[Report], answers: &Answers, day: Day) -> Vec<Drift> {
+    reports
+        .iter()
+        .filter(|report| report.day == day)
+        .filter_map(|report| {
+            let part = match report.name.as_str() {
+                "part_1" => 1,
+                "part_2" => 2,
+                _ => return None,
+            };
+            let actual = report.answer.as_ref()?;
+
+            match answers.check(part, actual) {
+                AnswerCheck::Drifted { expected } => Some(Drift {
+                    day,
+                    part,
+                    expected,
+                    actual: actual.clone(),
+                }),
+                _ => None,
+            }
+        })
+        .collect()
+}
+
+pub fn run_multi(
+    days_to_run: &HashSet<Day>,
+    is_release: bool,
+    bench: Option<BenchOptions>,
+    timeout: Option<Duration>,
+    track_memory: bool,
+    threads: Option<usize>,
+) -> RunSummary {
+    // children read the environment themselves, only an explicit timeout has to be passed on.
+    let child_timeout = timeout;
+    let timeout = timeout.or_else(runner::timeout_from_env);
+
     let mut timings: Vec<Timing> = Vec::with_capacity(days_to_run.len());
+    let mut drift: Vec<Drift> = vec![];
+    let mut timed_out: Vec<Day> = vec![];
+    let mut failures: Vec<String> = vec![];
 
     let mut need_space = false;
 
@@ -24,17 +102,80 @@ pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -
             println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
             println!("------");
 
-            let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
+            let output = match in_process(day, is_release, timeout) {
+                Some(solution) => {
+                    runner::set_bench_options(bench);
+                    Some(registry::run_solution(solution))
+                }
+                None => child_commands::run_solution(
+                    day,
+                    bench.as_ref(),
+                    is_release,
+                    child_timeout,
+                    timeout.map(|timeout| child_time_limit(timeout, bench.as_ref())),
+                    track_memory,
+                    threads,
+                )
+                .unwrap(),
+            };
+
+            match output {
+                None => println!("Not solved."),
+                Some(output) => {
+                    drift.extend(find_drift(&output.reports, &Answers::read(day), day));
+
+                    let panics = find_panics(&output.reports, day);
+                    if output.failed && panics.is_empty() {
+                        failures.push(format!("💥 Day {day}: exited with an error"));
+                    }
+                    failures.extend(panics);
+
+                    // partial timings of a day that timed out or failed are not meaningful.
+                    if output.timed_out {
+                        timed_out.push(day);
+                    } else if !output.failed {
+                        let mut timing = child_commands::timing_from_reports(&output.reports, day);
+                        timing.bench = bench;
+                        // children without `--threads` use rayon's default, which is the same as here.
+                        timing.threads = Some(threads.unwrap_or_else(rayon::current_num_threads));
+                        timings.push(timing);
+                    }
+                }
+            }
+        });
+
+    if !timed_out.is_empty() {
+        let days = timed_out
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join(", ");
+        println!("\n{ANSI_BOLD}Timed out:{ANSI_RESET} {days}");
+    }
 
-            if output.is_empty() {
-                println!("Not solved.");
+    if !failures.is_empty() {
+        println!("\n{ANSI_BOLD}Failed:{ANSI_RESET}");
+        failures.iter().for_each(|failure| println!("{failure}"));
+    }
+
+    if !drift.is_empty() {
+        println!("\n{ANSI_BOLD}Answers differing from confirmed answers:{ANSI_RESET}");
+        for Drift {
+            day,
+            part,
+            expected,
+            actual,
+        } in &drift
+        {
+            if expected.contains('\n') || actual.contains('\n') {
+                println!("✖ Day {day} part {part}:\nexpected:\n{expected}\nactual:\n{actual}");
             } else {
-                let val = child_commands::parse_exec_time(&output, day);
-                timings.push(val);
+                println!("✖ Day {day} part {part}: expected {expected}, got {actual}");
             }
-        });
+        }
+    }
 
-    if is_timed {
+    let timings = if bench.is_some() {
         let timings = Timings { data: timings };
         let total_millis = timings.total_millis();
         println!(
@@ -43,9 +184,28 @@ pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -
         Some(timings)
     } else {
         None
+    };
+
+    RunSummary {
+        timings,
+        failed: !timed_out.is_empty() || !failures.is_empty(),
     }
 }
 
+/// The registered solution of `day`, if it can run in this process. A part that exceeds the time limit
+/// can only be stopped by killing its process, and the solution has to run in the profile this binary was built with.
+fn in_process(
+    day: Day,
+    is_release: bool,
+    timeout: Option<Duration>,
+) -> Option<&'static Registered> {
+    if timeout.is_some() || is_release == cfg!(debug_assertions) {
+        return None;
+    }
+
+    registry::find(Year::selected(), day)
+}
+
 #[allow(dead_code)]
 #[derive(Debug)]
 pub enum Error {
@@ -59,45 +219,108 @@ impl From<std::io::Error> for Error {
     }
 }
 
-#[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
+/// Extra time a solution binary gets for starting up and printing its results.
+const CHILD_STARTUP_ALLOWANCE: Duration = Duration::from_secs(5);
+
+/// Wall-clock limit of a solution binary if each of its phases (parsing and both parts) may run for `timeout`.
+/// This catches a binary that hangs outside of the phases watched by the runner itself.
+pub fn child_time_limit(timeout: Duration, bench: Option<&BenchOptions>) -> Duration {
+    let phase = match bench {
+        // benching takes at least the minimum samples plus warmup, or about the bench time if the part is fast.
+        Some(bench) => {
+            let runs = bench.min_samples + bench.warmup_runs(bench.min_samples);
+            timeout + timeout * u32::try_from(runs).unwrap_or(u32::MAX) + bench.time * 2
+        }
+        None => timeout,
+    };
+
+    phase * 3 + CHILD_STARTUP_ALLOWANCE
 }
 
 /// All solutions live in isolated binaries.
-/// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
+/// This module encapsulates interaction with these binaries, both invoking them as well as collecting the reports they emit.
 pub mod child_commands {
-    use super::{get_path_for_bin, Error};
-    use crate::template::Day;
+    use super::Error;
+    use crate::template::{
+        layout,
+        report::Report,
+        runner::{format_duration_arg, TIMEOUT_EXIT_CODE},
+        BenchOptions, Day,
+    };
     use std::{
         io::{BufRead, BufReader},
-        path::Path,
-        process::{Command, Stdio},
+        process::{Child, Command, ExitStatus, Stdio},
         thread,
+        time::{Duration, Instant},
     };
 
-    /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
+    /// Everything a solution bin reported before it exited.
+    pub struct ChildOutput {
+        pub reports: Vec<Report>,
+        /// Whether a part exceeded the timeout or the process exceeded its time limit.
+        pub timed_out: bool,
+        /// Whether the process exited with an error for another reason, e.g. a panic.
+        pub failed: bool,
+    }
+
+    /// How often a child process with a time limit is checked for completion.
+    const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+    /// Run the solution bin for a given day and collect its [`Report`]s.
+    /// `timeout` and `threads` are passed on to the solution, the process is killed after `time_limit`.
+    /// Returns [`None`] if the day has not been scaffolded yet.
+    pub fn run_solution(
+        day: Day,
+        bench: Option<&BenchOptions>,
+        is_release: bool,
+        timeout: Option<Duration>,
+        time_limit: Option<Duration>,
+        track_memory: bool,
+        threads: Option<usize>,
+    ) -> Result<Option<ChildOutput>, Error> {
         // skip command invocation for days that have not been scaffolded yet.
-        if !Path::new(&get_path_for_bin(day)).exists() {
-            return Ok(vec![]);
+        if !layout::bin_path(day).exists() {
+            return Ok(None);
         }
 
-        let day_padded = day.to_string();
-        let mut args = vec!["run", "--quiet", "--bin", &day_padded];
+        let bin_name = layout::bin_name(day);
+        let mut build_args = vec!["build", "--quiet", "--bin", &bin_name];
 
         if is_release {
-            args.push("--release");
+            build_args.push("--release");
+        }
+
+        // compile ahead of time, so that compilation doesn't count towards the time limit.
+        if time_limit.is_some() {
+            Command::new("cargo").args(&build_args).status()?;
         }
 
-        if is_timed {
-            // mirror `--time` flag to child invocations.
-            args.push("--");
-            args.push("--time");
+        let timeout_arg = timeout.map(format_duration_arg);
+        let threads_arg = threads.map(|threads| threads.to_string());
+        let mut args = build_args;
+        args[0] = "run";
+
+        // reports are written to stdout, human-readable output to stderr.
+        args.extend(["--", "--format", "json"]);
+
+        if let Some(timeout_arg) = &timeout_arg {
+            args.extend(["--timeout", timeout_arg]);
         }
 
+        if track_memory {
+            args.push("--mem");
+        }
+
+        if let Some(threads_arg) = &threads_arg {
+            args.extend(["--threads", threads_arg]);
+        }
+
+        // mirror `--time` and the bench settings to child invocations.
+        let bench_args = bench.map(BenchOptions::to_args).unwrap_or_default();
+        args.extend(bench_args.iter().map(String::as_str));
+
         // spawn child command with piped stdout/stderr.
-        // forward output to stdout/stderr while grabbing stdout lines.
+        // forward stderr while collecting the reports from stdout.
 
         let mut cmd = Command::new("cargo")
             .args(&args)
@@ -108,87 +331,124 @@ pub mod child_commands {
         let stdout = BufReader::new(cmd.stdout.take().ok_or(super::Error::BrokenPipe)?);
         let stderr = BufReader::new(cmd.stderr.take().ok_or(super::Error::BrokenPipe)?);
 
-        let mut output = vec![];
-
-        let thread = thread::spawn(move || {
+        let stderr_thread = thread::spawn(move || {
             stderr.lines().for_each(|line| {
                 eprintln!("{}", line.unwrap());
             });
         });
 
-        for line in stdout.lines() {
-            let line = line.unwrap();
-            println!("{line}");
-            output.push(line);
-        }
+        let stdout_thread = thread::spawn(move || {
+            let mut reports = vec![];
+            for line in stdout.lines() {
+                let line = line.unwrap();
+                match Report::from_json_line(&line) {
+                    Some(report) => reports.push(report),
+                    // output printed by the solution itself.
+                    None => println!("{line}"),
+                }
+            }
+            reports
+        });
 
-        thread.join().unwrap();
-        cmd.wait()?;
+        let status = match time_limit {
+            Some(time_limit) => wait_with_limit(&mut cmd, time_limit)?,
+            None => Some(cmd.wait()?),
+        };
 
-        Ok(output)
+        let reports = stdout_thread.join().unwrap();
+        stderr_thread.join().unwrap();
+
+        // a part that exceeds the timeout is reported by the solution itself, see `runner::timeout`.
+        let (timed_out, failed) = match status {
+            Some(status) if status.code() == Some(TIMEOUT_EXIT_CODE) => (true, false),
+            Some(status) => (false, !status.success()),
+            None => {
+                eprintln!(
+                    "Day {day}: ⏱ killed after {:.1?}",
+                    time_limit.unwrap_or_default()
+                );
+                (true, false)
+            }
+        };
+
+        Ok(Some(ChildOutput {
+            reports,
+            timed_out,
+            failed,
+        }))
     }
 
-    pub fn parse_exec_time(output: &[String], day: Day) -> super::Timing {
+    /// Waits for `cmd` to exit, killing it once `time_limit` has passed. Returns [`None`] if it was killed.
+    fn wait_with_limit(cmd: &mut Child, time_limit: Duration) -> Result<Option<ExitStatus>, Error> {
+        let deadline = Instant::now() + time_limit;
+
+        loop {
+            if let Some(status) = cmd.try_wait()? {
+                return Ok(Some(status));
+            }
+
+            if Instant::now() >= deadline {
+                // `cargo run` replaces itself with the solution binary on unix, so this kills the solution.
+                cmd.kill()?;
+                cmd.wait()?;
+                return Ok(None);
+            }
+
+            thread::sleep(POLL_INTERVAL);
+        }
+    }
+
+    pub fn timing_from_reports(reports: &[Report], day: Day) -> super::Timing {
         let mut timings = super::Timing {
             day,
+            parse: None,
             part_1: None,
             part_2: None,
             total_nanos: 0_f64,
+            parse_memory: None,
+            part_1_memory: None,
+            part_2_memory: None,
+            parse_stats: None,
+            part_1_stats: None,
+            part_2_stats: None,
+            bench: None,
+            threads: None,
         };
 
-        output
+        reports
             .iter()
-            .filter_map(|l| {
-                if !l.contains(" samples)") {
-                    return None;
+            .filter(|report| report.day == day)
+            .for_each(|report| {
+                let timing_str = format!("{:.1?}", report.stats.mean);
+
+                match report.name.as_str() {
+                    "parse" => {
+                        timings.parse = Some(timing_str);
+                        timings.parse_memory = report.memory;
+                        timings.parse_stats = Some(report.stats.summary());
+                    }
+                    // parts without an answer are not timed.
+                    _ if report.answer.is_none() => return,
+                    "part_1" => {
+                        timings.part_1 = Some(timing_str);
+                        timings.part_1_memory = report.memory;
+                        timings.part_1_stats = Some(report.stats.summary());
+                    }
+                    "part_2" => {
+                        timings.part_2 = Some(timing_str);
+                        timings.part_2_memory = report.memory;
+                        timings.part_2_stats = Some(report.stats.summary());
+                    }
+                    // variants are cross-checked by the solution binary, only the parts are timed.
+                    _ => return,
                 }
 
-                let Some((timing_str, nanos)) = parse_time(l) else {
-                    eprintln!("Could not parse timings from line: {l}");
-                    return None;
-                };
-
-                let part = l.split(':').next()?;
-                Some((part, timing_str, nanos))
-            })
-            .for_each(|(part, timing_str, nanos)| {
-                if part.contains("Part 1") {
-                    timings.part_1 = Some(timing_str.into());
-                } else if part.contains("Part 2") {
-                    timings.part_2 = Some(timing_str.into());
-                }
-
-                timings.total_nanos += nanos;
+                timings.total_nanos += report.nanos();
             });
 
         timings
     }
 
-    fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
-        s.split(postfix).next()?.parse().ok()
-    }
-
-    fn parse_time(line: &str) -> Option<(&str, f64)> {
-        // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
-        let str_timing = line
-            .split(" samples)")
-            .next()?
-            .split('(')
-            .last()?
-            .split('@')
-            .next()?
-            .trim();
-
-        let parsed_timing = match str_timing {
-            s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
-            s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
-            s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
-            s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
-        }?;
-
-        Some((str_timing, parsed_timing))
-    }
-
     /// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
     #[cfg(feature = "test_lib")]
     macro_rules! assert_approx_eq {
@@ -205,47 +465,86 @@ pub mod child_commands {
 
     #[cfg(feature = "test_lib")]
     mod tests {
-        use super::parse_exec_time;
+        use super::timing_from_reports;
+        use std::time::Duration;
+
+        use crate::{
+            day,
+            template::{report::Report, runner::BenchStats, Day},
+        };
 
-        use crate::day;
+        fn report(day: Day, name: &str, answer: Option<&str>, nanos: u64) -> Report {
+            let mut stats = BenchStats::single(Duration::from_nanos(nanos));
+            stats.samples = 100;
+
+            Report {
+                day,
+                name: name.into(),
+                answer: answer.map(Into::into),
+                stats,
+                memory: None,
+                error: None,
+            }
+        }
+
+        #[test]
+        fn collects_execution_times() {
+            let res = timing_from_reports(
+                &[
+                    report(day!(1), "part_1", Some("0"), 74),
+                    report(day!(1), "part_2", Some("10"), 74_130_000),
+                ],
+                day!(1),
+            );
+            assert_approx_eq!(res.total_nanos, 74130074_f64);
+            assert_eq!(res.parse, None);
+            assert_eq!(res.part_1.unwrap(), "74.0ns");
+            assert_eq!(res.part_2.unwrap(), "74.1ms");
+        }
 
         #[test]
-        fn parses_execution_times() {
-            let res = parse_exec_time(
+        fn collects_parse_time() {
+            let res = timing_from_reports(
                 &[
-                    "Part 1: 0 (74.13ns @ 100000 samples)".into(),
-                    "Part 2: 10 (74.13ms @ 99999 samples)".into(),
-                    "".into(),
+                    report(day!(1), "parse", None, 1_500),
+                    report(day!(1), "part_1", Some("0"), 74),
+                    report(day!(1), "part_2", None, 0),
                 ],
                 day!(1),
             );
-            assert_approx_eq!(res.total_nanos, 74130074.13_f64);
-            assert_eq!(res.part_1.unwrap(), "74.13ns");
-            assert_eq!(res.part_2.unwrap(), "74.13ms");
+            assert_approx_eq!(res.total_nanos, 1574_f64);
+            assert_eq!(res.parse.unwrap(), "1.5µs");
+            assert_eq!(res.part_1.unwrap(), "74.0ns");
+            assert!(res.part_2.is_none());
         }
 
         #[test]
-        fn parses_with_patterns_in_input() {
-            let res = parse_exec_time(
+        fn collects_with_patterns_in_answers() {
+            let line = report(
+                day!(1),
+                "part_1",
+                Some("@ @ @ ( ) ms (2s @ 5 samples)"),
+                2_000_000_000,
+            )
+            .to_json_line();
+            let res = timing_from_reports(
                 &[
-                    "Part 1: @ @ @ ( ) ms (2s @ 5 samples)".into(),
-                    "Part 2: 10s (100ms @ 1 samples)".into(),
-                    "".into(),
+                    Report::from_json_line(&line).unwrap(),
+                    report(day!(1), "part_2", Some("10s"), 100_000_000),
                 ],
                 day!(1),
             );
             assert_approx_eq!(res.total_nanos, 2100000000_f64);
-            assert_eq!(res.part_1.unwrap(), "2s");
-            assert_eq!(res.part_2.unwrap(), "100ms");
+            assert_eq!(res.part_1.unwrap(), "2.0s");
+            assert_eq!(res.part_2.unwrap(), "100.0ms");
         }
 
         #[test]
-        fn parses_missing_parts() {
-            let res = parse_exec_time(
+        fn collects_missing_parts() {
+            let res = timing_from_reports(
                 &[
-                    "Part 1: ✖        ".into(),
-                    "Part 2: ✖        ".into(),
-                    "".into(),
+                    report(day!(1), "part_1", None, 10),
+                    report(day!(1), "part_2", None, 10),
                 ],
                 day!(1),
             );
@@ -255,3 +554,78 @@ pub mod child_commands {
         }
     }
 }
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{find_drift, find_panics, Drift};
+    use std::time::Duration;
+
+    use crate::{
+        day,
+        template::{answers::Answers, report::Report, runner::BenchStats},
+    };
+
+    #[test]
+    fn finds_drifted_answers() {
+        let report = |name: &str, answer: Option<&str>| Report {
+            day: day!(2),
+            name: name.into(),
+            answer: answer.map(Into::into),
+            stats: BenchStats::single(Duration::from_nanos(10)),
+            memory: None,
+            error: None,
+        };
+        let answers = Answers {
+            part_1: Some("42".into()),
+            part_2: Some("7".into()),
+        };
+
+        assert_eq!(
+            find_drift(
+                &[
+                    report("parse", None),
+                    report("part_1", Some("43")),
+                    report("part_2", Some("7"))
+                ],
+                &answers,
+                day!(2)
+            ),
+            vec![Drift {
+                day: day!(2),
+                part: 1,
+                expected: "42".into(),
+                actual: "43".into()
+            }]
+        );
+
+        // missing answers and unconfirmed parts are not drift.
+        assert!(find_drift(&[report("part_1", None)], &answers, day!(2)).is_empty());
+        assert!(
+            find_drift(&[report("part_1", Some("1"))], &Answers::default(), day!(2)).is_empty()
+        );
+    }
+
+    #[test]
+    fn finds_panics() {
+        let report = |name: &str, error: Option<&str>| Report {
+            day: day!(7),
+            name: name.into(),
+            answer: None,
+            stats: BenchStats::single(Duration::from_nanos(10)),
+            memory: None,
+            error: error.map(Into::into),
+        };
+
+        assert_eq!(
+            find_panics(
+                &[
+                    report("part_1", Some("invalid input @ src/bin/07.rs:12:5")),
+                    report("part_2", None)
+                ],
+                day!(7)
+            ),
+            vec!["💥 Day 07 part 1: invalid input @ src/bin/07.rs:12:5".to_string()]
+        );
+        assert!(find_panics(&[report("parse", None)], day!(7)).is_empty());
+    }
+}
@@ -1,67 +1,810 @@
 /// Encapsulates code that interacts with solution functions.
-use std::fmt::Display;
+use std::any::Any;
+use std::cell::{Cell, RefCell};
+use std::fmt::{self, Display};
 use std::hint::black_box;
-use std::io::{stdout, Write};
-use std::process::Output;
+use std::io::{stderr, stdout, Write};
+use std::panic::{self, AssertUnwindSafe, PanicHookInfo};
+use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::{Mutex, Once, OnceLock};
 use std::time::{Duration, Instant};
-use std::{cmp, env, process};
+use std::{env, fs, process, thread};
 
+use crate::template::answers::{self, AnswerCheck, Answers};
+use crate::template::memory::{self, format_bytes, AllocStats};
+use crate::template::report::Report;
+use crate::template::submissions::{self, Submission, Submissions, Verdict};
 use crate::template::ANSI_BOLD;
-use crate::template::{aoc_cli, Day, ANSI_ITALIC, ANSI_RESET};
+use crate::template::{
+    aoc_cli, layout, Answer, BenchOptions, Ctx, Day, InputSource, Part, Solution, Year,
+    ANSI_ITALIC, ANSI_RESET,
+};
 
-pub fn run_part<I: Clone, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
+/// Output format of the runner, selected with `--format <human|json>`.
+///  1. `human` prints results and timings to stdout.
+///  2. `json` prints one [`Report`] per line to stdout and moves the human-readable output to stderr.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Human,
+    Json,
+}
+
+impl Format {
+    /// Reads `--format <value>` or `--format=<value>` from `args`, [`Format::Human`] if it isn't passed.
+    pub fn from_args(args: &[String]) -> Result<Self, String> {
+        for (i, arg) in args.iter().enumerate() {
+            if arg == "--format" {
+                return match args.get(i + 1) {
+                    Some(value) if !value.starts_with("--") => value.parse(),
+                    _ => Err("`--format` expects a value, `human` or `json`.".into()),
+                };
+            }
+            if let Some(value) = arg.strip_prefix("--format=") {
+                return value.parse();
+            }
+        }
+
+        Ok(Self::Human)
+    }
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "human" => Ok(Self::Human),
+            "json" => Ok(Self::Json),
+            _ => Err(format!(
+                "Unknown format \"{s}\", expected `human` or `json`."
+            )),
+        }
+    }
+}
+
+impl Display for Format {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Human => "human",
+            Self::Json => "json",
+        })
+    }
+}
+
+pub fn format() -> Format {
+    static FORMAT: OnceLock<Format> = OnceLock::new();
+
+    *FORMAT.get_or_init(|| {
+        let args: Vec<String> = env::args().collect();
+        Format::from_args(&args).unwrap_or_else(|e| {
+            eprintln!("{e}");
+            process::exit(1);
+        })
+    })
+}
+
+/// Exit code of a solution binary that was stopped by the [`timeout`] watchdog.
+pub const TIMEOUT_EXIT_CODE: i32 = 124;
+
+/// Name of the environment variable that configures the default [`timeout`], e.g. in `.cargo/config.toml`.
+pub const TIMEOUT_ENV_VAR: &str = "AOC_TIMEOUT";
+
+/// Stack size of the worker thread that runs a solution part, same as the main thread on linux.
+const WORKER_STACK_SIZE: usize = 8 * 1024 * 1024;
+
+/// Time limit for a single run of a solution part, set with `--timeout <duration>` or the
+/// [`TIMEOUT_ENV_VAR`] environment variable. Benching repeats the run without a limit.
+pub fn timeout() -> Option<Duration> {
+    static TIMEOUT: OnceLock<Option<Duration>> = OnceLock::new();
+
+    *TIMEOUT.get_or_init(|| {
+        let args: Vec<String> = env::args().collect();
+        match args.iter().position(|x| x == "--timeout") {
+            Some(i) => match parse_duration(args.get(i + 1)?) {
+                Ok(timeout) => Some(timeout),
+                Err(e) => {
+                    eprintln!("Ignoring invalid timeout: {e}");
+                    None
+                }
+            },
+            None => timeout_from_env(),
+        }
+    })
+}
+
+/// The default timeout configured with the [`TIMEOUT_ENV_VAR`] environment variable.
+pub fn timeout_from_env() -> Option<Duration> {
+    let value = env::var(TIMEOUT_ENV_VAR).ok()?;
+
+    match parse_duration(&value) {
+        Ok(timeout) => Some(timeout),
+        Err(e) => {
+            eprintln!("Ignoring invalid {TIMEOUT_ENV_VAR}: {e}");
+            None
+        }
+    }
+}
+
+/// Formats `duration` so that [`parse_duration`] reads it back, e.g. to pass it to a child process.
+pub fn format_duration_arg(duration: Duration) -> String {
+    format!("{}s", duration.as_secs_f64())
+}
+
+/// Parses a duration like `500ms`, `10s`, `1.5m` or `1h`. A number without unit is in seconds.
+pub fn parse_duration(s: &str) -> Result<Duration, String> {
+    let s = s.trim();
+    let split = s
+        .find(|c: char| !c.is_ascii_digit() && c != '.')
+        .unwrap_or(s.len());
+    let (value, unit) = s.split_at(split);
+
+    let value: f64 = value
+        .parse()
+        .map_err(|_| format!("\"{s}\" is not a valid duration."))?;
+
+    let seconds = match unit.trim() {
+        "ms" => value / 1000.0,
+        "" | "s" => value,
+        "m" => value * 60.0,
+        "h" => value * 3600.0,
+        unit => {
+            return Err(format!(
+                "Unknown duration unit \"{unit}\", expected ms, s, m or h."
+            ))
+        }
+    };
+
+    Duration::try_from_secs_f64(seconds).map_err(|e| e.to_string())
+}
+
+/// Prints human-readable output, which goes to stderr if stdout is reserved for JSON reports.
+macro_rules! human {
+    ($($arg:tt)*) => {{
+        if format() == Format::Json {
+            eprint!($($arg)*);
+        } else {
+            print!($($arg)*);
+        }
+    }};
+}
+
+macro_rules! humanln {
+    ($($arg:tt)*) => {{
+        if format() == Format::Json {
+            eprintln!($($arg)*);
+        } else {
+            println!($($arg)*);
+        }
+    }};
+}
+
+fn flush() {
+    if format() == Format::Json {
+        let _ = stderr().flush();
+    } else {
+        let _ = stdout().flush();
+    }
+}
+
+fn emit_report(
+    day: Day,
+    name: &str,
+    answer: Option<String>,
+    stats: BenchStats,
+    memory: Option<AllocStats>,
+    error: Option<String>,
+) {
+    let report = Report {
+        day,
+        name: name.into(),
+        answer,
+        stats: if keep_samples() {
+            stats
+        } else {
+            stats.summary()
+        },
+        memory,
+        error,
+    };
+
+    if format() == Format::Json {
+        println!("{}", report.to_json_line());
+    }
+
+    REPORTS.lock().unwrap().push(report);
+}
+
+/// Reports of the phases that ran in this process, see [`take_reports`].
+static REPORTS: Mutex<Vec<Report>> = Mutex::new(vec![]);
+
+/// Takes the reports emitted so far, e.g. after running a solution in-process.
+pub fn take_reports() -> Vec<Report> {
+    std::mem::take(&mut *REPORTS.lock().unwrap())
+}
+
+/// The input the solution runs on, see [`InputSource`].
+pub fn input_source() -> &'static InputSource {
+    static INPUT_SOURCE: OnceLock<InputSource> = OnceLock::new();
+
+    INPUT_SOURCE.get_or_init(|| {
+        let args: Vec<String> = env::args().collect();
+        InputSource::from_args(&args).unwrap_or_else(|e| {
+            eprintln!("{e}");
+            process::exit(1);
+        })
+    })
+}
+
+/// Reads the input of `day` from the selected [`InputSource`]. Inputs other than the puzzle input are announced.
+pub fn read_input(day: Day) -> String {
+    let source = input_source();
+
+    let input = source.read(day).unwrap_or_else(|e| {
+        eprintln!("Could not read input from {}: {e}", source.describe(day));
+        process::exit(1);
+    });
+
+    if !source.is_puzzle_input() {
+        humanln!("{ANSI_ITALIC}Input: {}{ANSI_RESET}", source.describe(day));
+    }
+
+    input
+}
+
+static BENCH_OPTIONS: OnceLock<Option<BenchOptions>> = OnceLock::new();
+
+/// Settings for benching the parts, [`None`] unless `--time` is passed. See [`BenchOptions`].
+pub fn bench_options() -> Option<BenchOptions> {
+    *BENCH_OPTIONS.get_or_init(|| {
+        let args: Vec<String> = env::args().collect();
+        BenchOptions::from_args(&args).unwrap_or_else(|e| {
+            eprintln!("{e}");
+            process::exit(1);
+        })
+    })
+}
+
+/// Parses the size of the rayon thread pool, e.g. `4`.
+pub fn parse_threads(s: &str) -> Result<usize, String> {
+    match s.trim().parse::<usize>() {
+        Ok(threads) if threads > 0 => Ok(threads),
+        _ => Err(format!("\"{s}\" is not a valid number of threads.")),
+    }
+}
+
+/// Number of threads in the global rayon pool. The pool is configured on first use,
+/// with the size passed as `--threads` or rayon's default (one thread per CPU or `RAYON_NUM_THREADS`).
+pub fn threads() -> usize {
+    static THREADS: OnceLock<usize> = OnceLock::new();
+
+    *THREADS.get_or_init(|| {
+        let args: Vec<String> = env::args().collect();
+        let threads = args
+            .iter()
+            .position(|x| x == "--threads")
+            .map(|i| parse_threads(args.get(i + 1).map_or("", String::as_str)))
+            .transpose()
+            .unwrap_or_else(|e| {
+                eprintln!("`--threads`: {e}");
+                process::exit(1);
+            });
+
+        if let Some(threads) = threads {
+            rayon::ThreadPoolBuilder::new()
+                .num_threads(threads)
+                .build_global()
+                .expect("rayon pool was already initialized");
+        }
+
+        rayon::current_num_threads()
+    })
+}
+
+/// Sets the [`bench_options`] of solutions that run in this process instead of parsing them from the arguments.
+/// They can only be set before the first part runs.
+pub fn set_bench_options(options: Option<BenchOptions>) {
+    if BENCH_OPTIONS.set(options).is_err() && bench_options() != options {
+        panic!("bench options can't be changed once a part ran");
+    }
+}
+
+/// Whether the [`Ctx`] of the parts stays inactive, enabled with `--quiet`.
+pub fn quiet() -> bool {
+    static QUIET: OnceLock<bool> = OnceLock::new();
+
+    *QUIET.get_or_init(|| env::args().any(|x| x == "--quiet"))
+}
+
+/// Writes the frames recorded by a part to `data/<year>/frames/DD-<name>.txt`, separated by empty lines.
+fn store_frames(day: Day, name: &str, ctx: &Ctx) {
+    let frames = ctx.take_frames();
+    if frames.is_empty() {
+        return;
+    }
+
+    let dir = layout::data_dir(Year::selected()).join("frames");
+    let path = dir.join(format!("{day}-{name}.txt"));
+    let stored = fs::create_dir_all(&dir).and_then(|()| fs::write(&path, frames.join("\n\n")));
+
+    match stored {
+        Ok(()) => humanln!(
+            "{ANSI_ITALIC}Recorded {} frames to {}{ANSI_RESET}",
+            frames.len(),
+            path.display()
+        ),
+        Err(e) => eprintln!("Failed to store frames: {e}"),
+    }
+}
+
+/// Whether the recorded samples are kept in the reports, enabled with `--samples`.
+pub fn keep_samples() -> bool {
+    static KEEP_SAMPLES: OnceLock<bool> = OnceLock::new();
+
+    *KEEP_SAMPLES.get_or_init(|| env::args().any(|x| x == "--samples"))
+}
+
+/// Whether allocations are counted, enabled with `--mem`.
+pub fn track_memory() -> bool {
+    static TRACK_MEMORY: OnceLock<bool> = OnceLock::new();
+
+    *TRACK_MEMORY.get_or_init(|| {
+        if !env::args().any(|x| x == "--mem") {
+            return false;
+        }
+
+        let is_available = memory::is_available();
+        if !is_available {
+            eprintln!(
+                "Ignoring `--mem`, allocations can't be counted in this binary, e.g. while DHAT is enabled."
+            );
+        }
+        is_available
+    })
+}
+
+/// Exit code of a solution binary in which parsing or a part panicked, same as for an uncaught panic.
+pub const PANIC_EXIT_CODE: i32 = 101;
+
+/// Set once parsing or a part panicked, see [`finish`].
+static FAILED: AtomicBool = AtomicBool::new(false);
+
+thread_local! {
+    /// Whether panics on this thread are caught by [`catch_panic`].
+    static CATCHING: Cell<bool> = const { Cell::new(false) };
+    /// Message and location of the last panic caught on this thread.
+    static LAST_PANIC: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+/// Runs `func`, turning a panic into an error with the panic message and location.
+/// The default panic output is suppressed for caught panics.
+fn catch_panic<T>(func: impl FnOnce() -> T) -> Result<T, String> {
+    static HOOK: Once = Once::new();
+
+    HOOK.call_once(|| {
+        let default_hook = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if CATCHING.get() {
+                LAST_PANIC.set(Some(format_panic(info)));
+            } else {
+                default_hook(info);
+            }
+        }));
+    });
+
+    CATCHING.set(true);
+    let result = panic::catch_unwind(AssertUnwindSafe(func));
+    CATCHING.set(false);
+
+    result.map_err(|payload| {
+        // panics on other threads (e.g. of rayon) only pass on their payload.
+        LAST_PANIC
+            .take()
+            .unwrap_or_else(|| panic_message(payload.as_ref()).into())
+    })
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> &str {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message
+    } else {
+        "Box<dyn Any>"
+    }
+}
+
+fn format_panic(info: &PanicHookInfo) -> String {
+    let message = panic_message(info.payload());
+    match info.location() {
+        Some(location) => {
+            // days compiled into the main binary are included by their absolute path, see `build.rs`.
+            let file = location
+                .file()
+                .strip_prefix(env!("CARGO_MANIFEST_DIR"))
+                .map_or(location.file(), |file| file.trim_start_matches(['/', '\\']));
+            format!(
+                "{message} @ {file}:{}:{}",
+                location.line(),
+                location.column()
+            )
+        }
+        None => message.into(),
+    }
+}
+
+fn print_panic(label: &str, message: &str) {
+    FAILED.store(true, Ordering::Relaxed);
+    human!("\r");
+    humanln!("{label}: 💥 {message}");
+}
+
+/// Whether parsing or a part panicked since the last call, e.g. after running a solution in-process.
+pub fn take_failed() -> bool {
+    FAILED.swap(false, Ordering::Relaxed)
+}
+
+/// Exits with [`PANIC_EXIT_CODE`] if parsing or a part panicked. Called at the end of the generated `main`.
+pub fn finish() {
+    if FAILED.load(Ordering::Relaxed) {
+        flush();
+        process::exit(PANIC_EXIT_CODE);
+    }
+}
+
+/// Answer and timing of a solution part that ran to completion.
+#[derive(Debug, Clone)]
+pub struct PartOutcome {
+    pub answer: Option<String>,
+    pub stats: BenchStats,
+}
+
+/// Runs a solution part and prints its result. Returns [`None`] if the part panicked.
+/// Parts may take a [`Ctx`] as second parameter, it is active during the first run only.
+pub fn run_part<I: Clone + Send, M, P: Part<I, M>>(
+    func: P,
+    input: I,
+    day: Day,
+    part: u8,
+) -> Option<PartOutcome>
+where
+    P::Output: Into<Answer> + Send,
+{
     let part_str = format!("Part {part}");
+    let ctx = Ctx::new(!quiet());
+    let func = |input: I| func.call(input, &ctx).map(Into::into);
+
+    let timed = run_timed(func, input, &part_str, |result| {
+        ctx.deactivate();
+        print_result(result, &part_str, "", "");
+    });
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+    // a panicking part is reported, the other part still runs.
+    let (result, stats, memory) = match timed {
+        Ok(timed) => timed,
+        Err((message, elapsed)) => {
+            let stats = BenchStats::single(elapsed);
+            print_panic(&part_str, &message);
+            store_frames(day, &format!("part_{part}"), &ctx);
+            emit_report(
+                day,
+                &format!("part_{part}"),
+                None,
+                stats,
+                None,
+                Some(message),
+            );
+            return None;
+        }
+    };
 
-    print_result(&result, &part_str, &format_duration(&duration, samples));
+    let answer = result.as_ref().map(ToString::to_string);
+    // confirmed answers only apply to the puzzle input.
+    let check = match answer.as_deref() {
+        Some(answer) if input_source().is_puzzle_input() => Answers::read(day).check(part, answer),
+        _ => AnswerCheck::Unknown,
+    };
 
-    if let Some(result) = result {
-        submit_result(result, day, part);
+    print_result(
+        &result,
+        &part_str,
+        &format_check(&check),
+        &format!("{}{}", format_stats(&stats), format_memory(memory.as_ref())),
+    );
+    emit_report(
+        day,
+        &format!("part_{part}"),
+        answer.clone(),
+        stats.clone(),
+        memory,
+        None,
+    );
+    store_frames(day, &format!("part_{part}"), &ctx);
+
+    if let Some(answer) = &answer {
+        if submit_result(answer, day, part) == Some(Verdict::Correct) {
+            match answers::confirm(day, part, answer.clone()) {
+                Ok(()) => humanln!("Stored confirmed answer for part {part}."),
+                Err(e) => eprintln!("Failed to store confirmed answer: {e}"),
+            }
+        }
     }
+
+    Some(PartOutcome { answer, stats })
 }
 
-/// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
-///  1. in debug, the function is executed once.
-///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
-fn run_timed<I: Clone, T>(
-    func: impl Fn(I) -> T,
+/// Infers the part a variant belongs to from its name, e.g. `part_two_naive` is a variant of part 2.
+pub fn variant_part(name: &str) -> Option<u8> {
+    if name.starts_with("part_one") {
+        Some(1)
+    } else if name.starts_with("part_two") {
+        Some(2)
+    } else {
+        None
+    }
+}
+
+/// Runs alternative implementations of the solution parts, registered with
+/// `solution!(DAY, variants: [...])`. Every variant is checked against the answer of its part
+/// and the timings of all implementations of a part are compared in [`Variants::finish`].
+pub struct Variants {
+    day: Day,
+    parts: [Option<PartOutcome>; 2],
+    timings: [Vec<(String, Duration)>; 2],
+}
+
+impl Variants {
+    /// Starts the comparison with the outcomes of `part_one` and `part_two`.
+    pub fn new(day: Day, part_1: Option<PartOutcome>, part_2: Option<PartOutcome>) -> Self {
+        let timing = |name: &str, outcome: &Option<PartOutcome>| {
+            outcome
+                .iter()
+                .map(|outcome| (name.to_string(), outcome.stats.mean))
+                .collect()
+        };
+
+        Self {
+            day,
+            timings: [timing("part_one", &part_1), timing("part_two", &part_2)],
+            parts: [part_1, part_2],
+        }
+    }
+
+    pub fn run<I: Clone + Send, M, P: Part<I, M>>(&mut self, name: &str, func: P, input: I)
+    where
+        P::Output: Into<Answer> + Send,
+    {
+        let Some(part) = variant_part(name) else {
+            FAILED.store(true, Ordering::Relaxed);
+            eprintln!("Variant `{name}` must start with `part_one` or `part_two`.");
+            return;
+        };
+
+        let ctx = Ctx::new(!quiet());
+        let func = |input: I| func.call(input, &ctx).map(Into::into);
+        let timed = run_timed(func, input, name, |result| {
+            ctx.deactivate();
+            print_result(result, name, "", "");
+        });
+
+        let (result, stats, memory) = match timed {
+            Ok(timed) => timed,
+            Err((message, elapsed)) => {
+                let stats = BenchStats::single(elapsed);
+                print_panic(name, &message);
+                store_frames(self.day, name, &ctx);
+                emit_report(self.day, name, None, stats, None, Some(message));
+                return;
+            }
+        };
+
+        let answer = result.as_ref().map(ToString::to_string);
+        let main = ["part_one", "part_two"][usize::from(part - 1)];
+        // without an answer of the main part there is nothing to compare against.
+        let check = match &self.parts[usize::from(part - 1)] {
+            Some(outcome) if outcome.answer == answer => AnswerCheck::Correct,
+            Some(outcome) => AnswerCheck::Drifted {
+                expected: outcome.answer.clone().unwrap_or_else(|| "✖".into()),
+            },
+            None => AnswerCheck::Unknown,
+        };
+
+        print_result(
+            &result,
+            name,
+            &format_check(&check),
+            &format!("{}{}", format_stats(&stats), format_memory(memory.as_ref())),
+        );
+
+        let error = if let AnswerCheck::Drifted { expected } = &check {
+            FAILED.store(true, Ordering::Relaxed);
+            Some(format!(
+                "answer {} differs from {main} ({expected})",
+                answer.as_deref().unwrap_or("✖")
+            ))
+        } else {
+            None
+        };
+        self.timings[usize::from(part - 1)].push((name.into(), stats.mean));
+
+        emit_report(self.day, name, answer, stats, memory, error);
+        store_frames(self.day, name, &ctx);
+    }
+
+    /// Prints the timings of every part that has variants side by side.
+    pub fn finish(self) {
+        for (part, timings) in self.timings.iter().enumerate() {
+            if timings.len() < 2 {
+                continue;
+            }
+
+            humanln!("\n{ANSI_BOLD}Part {} variants{ANSI_RESET}", part + 1);
+            for line in format_comparison(timings) {
+                humanln!("{line}");
+            }
+        }
+    }
+}
+
+/// Formats the mean run times of implementations of a part as table rows, relative to the first one.
+fn format_comparison(timings: &[(String, Duration)]) -> Vec<String> {
+    let Some((_, baseline)) = timings.first() else {
+        return vec![];
+    };
+
+    let name_width = timings
+        .iter()
+        .map(|(name, _)| name.len())
+        .max()
+        .unwrap_or(0);
+    let times = timings
+        .iter()
+        .map(|(_, time)| format!("{time:.1?}"))
+        .collect::<Vec<_>>();
+    let time_width = times.iter().map(|t| t.chars().count()).max().unwrap_or(0);
+
+    timings
+        .iter()
+        .zip(times)
+        .map(|((name, time), time_str)| {
+            let factor = time.as_secs_f64() / baseline.as_secs_f64().max(f64::MIN_POSITIVE);
+            format!("  {name:<name_width$}  {time_str:>time_width$}  {factor:>6.2}x")
+        })
+        .collect()
+}
+
+/// Run a [`Solution`]: the input is parsed (and timed) once, then both parts are run on the parsed input.
+pub fn run_solution<S: Solution>(input: &str, day: Day) {
+    let timed = run_timed(S::parse, input, "Parse", |_| {
+        human!("Parse: ");
+        flush();
+    });
+
+    // without a parsed input neither part can run.
+    let (parsed, stats, memory) = match timed {
+        Ok(timed) => timed,
+        Err((message, elapsed)) => {
+            let stats = BenchStats::single(elapsed);
+            print_panic("Parse", &message);
+            emit_report(day, "parse", None, stats, None, Some(message));
+            return;
+        }
+    };
+
+    human!("\r");
+    humanln!(
+        "Parse:{}{}",
+        format_stats(&stats),
+        format_memory(memory.as_ref())
+    );
+    emit_report(day, "parse", None, stats, memory, None);
+
+    run_part(S::part_one, &parsed, day, 1);
+    run_part(S::part_two, &parsed, day, 2);
+}
+
+/// Run a solution part. The behavior differs depending on whether `--time` is passed:
+///  1. without it, the function is executed once.
+///  2. with it, the function is benched according to the [`bench_options`] (by default approx. 1 second
+///     of execution time or 10 samples, whatever takes longer.)
+///
+/// With `--mem`, the allocations of the first execution are counted. Benching is not affected by this.
+///
+/// If the function panics, the panic message and the time until the panic are returned instead.
+fn run_timed<I: Clone + Send, T: Send>(
+    func: impl Fn(I) -> T + Sync,
     input: I,
+    label: &str,
     hook: impl Fn(&T),
-) -> (T, Duration, u128) {
-    let timer = Instant::now();
-    let result = {
-        let input = input.clone();
+) -> Result<(T, BenchStats, Option<AllocStats>), (String, Duration)> {
+    // the pool has to be set up before a solution uses rayon for the first time.
+    threads();
 
-        #[cfg(feature = "dhat-heap")]
-        let _profiler = dhat::Profiler::new_heap();
+    let (result, base_time, memory) = run_watched(
+        |input| {
+            let timer = Instant::now();
 
-        func(input)
-    };
-    let base_time = timer.elapsed();
+            #[cfg(feature = "dhat-heap")]
+            let _profiler = dhat::Profiler::new_heap();
+
+            let (result, memory) = if track_memory() {
+                let (result, memory) = memory::measure(|| catch_panic(|| func(input)));
+                (result, Some(memory))
+            } else {
+                (catch_panic(|| func(input)), None)
+            };
+            (result, timer.elapsed(), memory)
+        },
+        input.clone(),
+        label,
+    );
+
+    let result = result.map_err(|message| (message, base_time))?;
 
     hook(&result);
 
-    let run = if std::env::args().any(|x| x == "--time") {
-        bench(func, input, &base_time)
-    } else {
-        (base_time, 1)
+    let stats = match bench_options() {
+        Some(options) => bench(func, input, &base_time, &options),
+        None => BenchStats::single(base_time),
     };
 
-    (result, run.0, run.1)
+    Ok((result, stats, memory))
 }
 
-fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
-    let mut stdout = stdout();
+/// Runs `func` on a worker thread if a [`timeout`] is set. If it doesn't return within the time limit,
+/// the part is reported as timed out and the process exits with [`TIMEOUT_EXIT_CODE`].
+fn run_watched<I: Send, T: Send>(func: impl Fn(I) -> T + Sync, input: I, label: &str) -> T {
+    let Some(limit) = timeout() else {
+        return func(input);
+    };
+
+    let (sender, receiver) = mpsc::channel();
+
+    thread::scope(|scope| {
+        let worker = thread::Builder::new()
+            .name(label.into())
+            .stack_size(WORKER_STACK_SIZE)
+            .spawn_scoped(scope, || {
+                // the receiver is gone if the part timed out, the result is irrelevant then.
+                let _ = sender.send(func(input));
+            })
+            .expect("failed to spawn worker thread");
+
+        match receiver.recv_timeout(limit) {
+            Ok(result) => result,
+            Err(RecvTimeoutError::Timeout) => {
+                human!("\r");
+                humanln!("{label}: ⏱ timed out after {limit:.1?}          ");
+                flush();
+                process::exit(TIMEOUT_EXIT_CODE);
+            }
+            // the worker panicked before sending a result, pass the panic on.
+            Err(RecvTimeoutError::Disconnected) => match worker.join() {
+                Err(payload) => std::panic::resume_unwind(payload),
+                Ok(()) => unreachable!("worker finished without sending a result"),
+            },
+        }
+    })
+}
+
+fn bench<I: Clone, T>(
+    func: impl Fn(I) -> T,
+    input: I,
+    base_time: &Duration,
+    options: &BenchOptions,
+) -> BenchStats {
+    human!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
+    flush();
 
-    print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
-    let _ = stdout.flush();
+    let bench_iterations = options.samples(*base_time);
 
-    let bench_iterations =
-        (Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10)).clamp(10, 10000);
+    // the first iterations warm up caches and the branch predictor, their timings are discarded.
+    for _ in 0..options.warmup_runs(bench_iterations) {
+        black_box(func(black_box(input.clone())));
+    }
 
-    let mut timers: Vec<Duration> = vec![];
+    let mut timers: Vec<Duration> = Vec::with_capacity(bench_iterations);
 
     for _ in 0..bench_iterations {
         // need a clone here to make the borrow checker happy.
@@ -71,11 +814,129 @@ fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) ->
         timers.push(timer.elapsed());
     }
 
-    (
-        #[allow(clippy::cast_possible_truncation)]
-        Duration::from_nanos(average_duration(&timers) as u64),
-        bench_iterations,
-    )
+    BenchStats::from_samples(&timers, options.filter_outliers)
+}
+
+/// Samples with a modified z-score above this threshold are considered outliers.
+const MAD_OUTLIER_THRESHOLD: f64 = 3.5;
+
+/// Summary statistics of the samples recorded while benching a solution part.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BenchStats {
+    /// Number of samples the statistics are computed from, excluding warmup and outliers.
+    pub samples: usize,
+    /// Number of samples that were discarded as outliers.
+    pub outliers: usize,
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub p95: Duration,
+    pub std_dev: Duration,
+    /// Every recorded sample in the order it was taken, without the warmup runs but with the outliers.
+    /// Only reported with [`--samples`](keep_samples).
+    pub recorded: Vec<Duration>,
+}
+
+impl BenchStats {
+    /// Statistics of a single, unbenched run.
+    pub fn single(duration: Duration) -> Self {
+        Self {
+            samples: 1,
+            outliers: 0,
+            min: duration,
+            median: duration,
+            mean: duration,
+            p95: duration,
+            std_dev: Duration::ZERO,
+            recorded: vec![duration],
+        }
+    }
+
+    /// The statistics without the recorded samples.
+    pub fn summary(&self) -> Self {
+        Self {
+            recorded: vec![],
+            ..self.clone()
+        }
+    }
+
+    /// Computes the statistics of the recorded samples, optionally discarding outliers first
+    /// using the median absolute deviation (MAD) method.
+    pub fn from_samples(samples: &[Duration], filter_outliers: bool) -> Self {
+        assert!(
+            !samples.is_empty(),
+            "can't compute statistics without samples"
+        );
+
+        let mut sorted = samples.to_vec();
+        sorted.sort_unstable();
+
+        let mut outliers = 0;
+        if filter_outliers {
+            let kept = remove_outliers(&sorted);
+            outliers = sorted.len() - kept.len();
+            sorted = kept;
+        }
+
+        let mean = average_duration(&sorted);
+        #[allow(clippy::cast_precision_loss)]
+        let variance = sorted
+            .iter()
+            .map(|d| (d.as_nanos() as f64 - mean as f64).powi(2))
+            .sum::<f64>()
+            / sorted.len() as f64;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        Self {
+            samples: sorted.len(),
+            outliers,
+            min: sorted[0],
+            median: median(&sorted),
+            mean: Duration::from_nanos(mean as u64),
+            p95: percentile(&sorted, 95),
+            std_dev: Duration::from_nanos(variance.sqrt() as u64),
+            recorded: samples.to_vec(),
+        }
+    }
+}
+
+/// Median of an already sorted, non-empty slice.
+fn median(sorted: &[Duration]) -> Duration {
+    let mid = sorted.len() / 2;
+    if sorted.len().is_multiple_of(2) {
+        (sorted[mid - 1] + sorted[mid]) / 2
+    } else {
+        sorted[mid]
+    }
+}
+
+/// Nearest-rank percentile of an already sorted, non-empty slice.
+fn percentile(sorted: &[Duration], p: usize) -> Duration {
+    let rank = (p * sorted.len()).div_ceil(100);
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+/// Keeps the samples whose modified z-score `0.6745 * |x - median| / MAD` is within the threshold.
+fn remove_outliers(sorted: &[Duration]) -> Vec<Duration> {
+    let median = median(sorted);
+
+    let mut deviations = sorted
+        .iter()
+        .map(|d| d.abs_diff(median))
+        .collect::<Vec<_>>();
+    deviations.sort_unstable();
+    let mad = self::median(&deviations).as_secs_f64();
+
+    // with more than half of the samples being identical there is nothing to filter.
+    if mad == 0.0 {
+        return sorted.to_vec();
+    }
+
+    sorted
+        .iter()
+        .filter(|d| 0.6745 * d.abs_diff(median).as_secs_f64() / mad <= MAD_OUTLIER_THRESHOLD)
+        .copied()
+        .collect()
 }
 
 fn average_duration(numbers: &[Duration]) -> u128 {
@@ -86,44 +947,77 @@ fn average_duration(numbers: &[Duration]) -> u128 {
         / numbers.len() as u128
 }
 
-fn format_duration(duration: &Duration, samples: u128) -> String {
-    if samples == 1 {
-        format!(" ({duration:.1?})")
+fn format_stats(stats: &BenchStats) -> String {
+    if stats.samples == 1 {
+        return format!(" ({:.1?})", stats.mean);
+    }
+
+    let outliers = if stats.outliers > 0 {
+        format!(", {} outliers removed", stats.outliers)
     } else {
-        format!(" ({duration:.1?} @ {samples} samples)")
+        String::new()
+    };
+
+    format!(
+        " ({:.1?} @ {} samples) [min {:.1?}, median {:.1?}, p95 {:.1?}, σ {:.1?}{outliers}]",
+        stats.mean, stats.samples, stats.min, stats.median, stats.p95, stats.std_dev
+    )
+}
+
+fn format_memory(memory: Option<&AllocStats>) -> String {
+    match memory {
+        Some(memory) => format!(
+            " [mem: peak {}, {} allocs, {} total]",
+            format_bytes(memory.peak_bytes),
+            memory.allocations,
+            format_bytes(memory.bytes)
+        ),
+        None => String::new(),
+    }
+}
+
+fn format_check(check: &AnswerCheck) -> String {
+    match check {
+        AnswerCheck::Unknown => String::new(),
+        AnswerCheck::Correct => " ✔".into(),
+        AnswerCheck::Drifted { expected } if expected.contains('\n') => {
+            " ✖ (differs from confirmed answer)".into()
+        }
+        AnswerCheck::Drifted { expected } => format!(" ✖ (expected {expected})"),
     }
 }
 
-fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str) {
+fn print_result(result: &Option<Answer>, part: &str, check_str: &str, duration_str: &str) {
     let is_intermediate_result = duration_str.is_empty();
 
     match result {
         Some(result) => {
-            if result.to_string().contains('\n') {
-                let str = format!("{part}: ▼ {duration_str}");
+            if result.is_multiline() {
+                let str = format!("{part}: ▼{check_str} {duration_str}");
                 if is_intermediate_result {
-                    print!("{str}");
+                    human!("{str}");
                 } else {
-                    print!("\r");
-                    println!("{str}");
-                    println!("{result}");
+                    human!("\r");
+                    humanln!("{str}");
+                    humanln!("{result}");
                 }
             } else {
-                let str = format!("{part}: {ANSI_BOLD}{result}{ANSI_RESET}{duration_str}");
+                let str =
+                    format!("{part}: {ANSI_BOLD}{result}{ANSI_RESET}{check_str}{duration_str}");
                 if is_intermediate_result {
-                    print!("{str}");
+                    human!("{str}");
                 } else {
-                    print!("\r");
-                    println!("{str}");
+                    human!("\r");
+                    humanln!("{str}");
                 }
             }
         }
         None => {
             if is_intermediate_result {
-                print!("{part}: ✖");
+                human!("{part}: ✖");
             } else {
-                print!("\r");
-                println!("{part}: ✖             ");
+                human!("\r");
+                humanln!("{part}: ✖             ");
             }
         }
     }
@@ -132,11 +1026,10 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
 /// Parse the arguments passed to `solve` and try to submit one part of the solution if:
 ///  1. we are in `--release` mode.
 ///  2. aoc-cli is installed.
-fn submit_result<T: Display>(
-    result: T,
-    day: Day,
-    part: u8,
-) -> Option<Result<Output, aoc_cli::AocCommandError>> {
+///  3. the answer isn't ruled out by an earlier submission and no cooldown is pending, see [`Submissions`].
+///
+/// Every submission is recorded with the verdict, which is returned.
+fn submit_result(result: &str, day: Day, part: u8) -> Option<Verdict> {
     let args: Vec<String> = env::args().collect();
 
     if !args.contains(&"--submit".into()) {
@@ -159,11 +1052,177 @@ fn submit_result<T: Display>(
         return None;
     }
 
+    if result.contains('\n') {
+        eprintln!("Not submitting, the answer is a drawing whose letters could not be recognized.");
+        return None;
+    }
+
+    if !input_source().is_puzzle_input() {
+        eprintln!(
+            "Not submitting, the answer was computed from {} instead of the puzzle input.",
+            input_source()
+        );
+        return None;
+    }
+
     if aoc_cli::check().is_err() {
         eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
         process::exit(1);
     }
 
-    println!("Submitting result via aoc-cli...");
-    Some(aoc_cli::submit(day, part, &result.to_string()))
+    let mut submissions = Submissions::read(day);
+    let timestamp = submissions::now();
+
+    if let Err(reason) = submissions.check(part, result, timestamp) {
+        eprintln!("Not submitting, {reason}");
+        return None;
+    }
+
+    humanln!("Submitting result via aoc-cli...");
+    let output = match aoc_cli::submit(day, part, result) {
+        Ok(output) | Err(aoc_cli::AocCommandError::BadExitStatus(output)) => output,
+        Err(e) => {
+            eprintln!("{e}");
+            return None;
+        }
+    };
+
+    let response = String::from_utf8_lossy(&output.stdout);
+    let verdict = Verdict::from_response(&response);
+
+    submissions.record(Submission {
+        part,
+        answer: result.into(),
+        verdict,
+        timestamp,
+        cooldown: submissions::parse_cooldown(&response),
+    });
+    if let Err(e) = submissions.store(day) {
+        eprintln!("Failed to store submission: {e}");
+    }
+
+    Some(verdict)
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{
+        format_comparison, format_duration_arg, parse_duration, parse_threads, variant_part,
+        BenchStats, Format,
+    };
+    use std::time::Duration;
+
+    #[test]
+    fn infers_variant_parts() {
+        assert_eq!(variant_part("part_one_naive"), Some(1));
+        assert_eq!(variant_part("part_two_bitgrid"), Some(2));
+        assert_eq!(variant_part("naive"), None);
+    }
+
+    #[test]
+    fn formats_variant_comparison() {
+        let timings = vec![
+            ("part_two".to_string(), Duration::from_micros(200)),
+            ("part_two_naive".to_string(), Duration::from_micros(1500)),
+        ];
+
+        assert_eq!(
+            format_comparison(&timings),
+            vec![
+                "  part_two        200.0µs    1.00x",
+                "  part_two_naive    1.5ms    7.50x",
+            ]
+        );
+        assert!(format_comparison(&[]).is_empty());
+    }
+
+    #[test]
+    fn parses_format() {
+        let args = |args: &[&str]| -> Vec<String> { args.iter().map(|&s| s.into()).collect() };
+
+        assert_eq!(Format::from_args(&args(&["01"])), Ok(Format::Human));
+        assert_eq!(
+            Format::from_args(&args(&["01", "--format", "json"])),
+            Ok(Format::Json)
+        );
+        assert_eq!(
+            Format::from_args(&args(&["01", "--format=json"])),
+            Ok(Format::Json)
+        );
+        assert_eq!(
+            Format::from_args(&args(&["01", "--format", "human"])),
+            Ok(Format::Human)
+        );
+        assert!(Format::from_args(&args(&["01", "--format", "jsno"])).is_err());
+        assert!(Format::from_args(&args(&["01", "--format"])).is_err());
+        assert!(Format::from_args(&args(&["01", "--format", "--mem"])).is_err());
+        assert_eq!(Format::Json.to_string().parse(), Ok(Format::Json));
+    }
+
+    #[test]
+    fn parses_threads() {
+        assert_eq!(parse_threads("4"), Ok(4));
+        assert!(parse_threads("0").is_err());
+        assert!(parse_threads("x").is_err());
+        assert!(parse_threads("").is_err());
+    }
+
+    #[test]
+    fn parses_durations() {
+        assert_eq!(parse_duration("500ms"), Ok(Duration::from_millis(500)));
+        assert_eq!(parse_duration("10s"), Ok(Duration::from_secs(10)));
+        assert_eq!(parse_duration("10"), Ok(Duration::from_secs(10)));
+        assert_eq!(parse_duration("1.5m"), Ok(Duration::from_secs(90)));
+        assert_eq!(parse_duration(" 2h "), Ok(Duration::from_secs(7200)));
+        assert!(parse_duration("10d").is_err());
+        assert!(parse_duration("s").is_err());
+        assert!(parse_duration("").is_err());
+
+        let duration = Duration::from_millis(1500);
+        assert_eq!(parse_duration(&format_duration_arg(duration)), Ok(duration));
+    }
+
+    fn micros(values: &[u64]) -> Vec<Duration> {
+        values.iter().map(|&v| Duration::from_micros(v)).collect()
+    }
+
+    #[test]
+    fn computes_bench_stats() {
+        let stats = BenchStats::from_samples(&micros(&[5, 1, 4, 2, 3]), false);
+        assert_eq!(stats.samples, 5);
+        assert_eq!(stats.outliers, 0);
+        assert_eq!(stats.min, Duration::from_micros(1));
+        assert_eq!(stats.median, Duration::from_micros(3));
+        assert_eq!(stats.mean, Duration::from_micros(3));
+        assert_eq!(stats.p95, Duration::from_micros(5));
+        assert_eq!(stats.std_dev, Duration::from_nanos(1414));
+    }
+
+    #[test]
+    fn computes_even_median() {
+        let stats = BenchStats::from_samples(&micros(&[1, 2, 3, 10]), false);
+        assert_eq!(stats.median, Duration::from_nanos(2500));
+    }
+
+    #[test]
+    fn removes_outliers() {
+        let samples = micros(&[10, 11, 9, 10, 12, 10, 11, 9, 500]);
+        let stats = BenchStats::from_samples(&samples, true);
+        assert_eq!(stats.samples, 8);
+        assert_eq!(stats.outliers, 1);
+        assert_eq!(stats.p95, Duration::from_micros(12));
+        // the recorded samples keep their order and the outliers.
+        assert_eq!(stats.recorded, samples);
+
+        let stats = BenchStats::from_samples(&samples, false);
+        assert_eq!(stats.samples, 9);
+        assert_eq!(stats.p95, Duration::from_micros(500));
+    }
+
+    #[test]
+    fn keeps_samples_without_deviation() {
+        let stats = BenchStats::from_samples(&micros(&[10, 10, 10, 10, 50]), true);
+        assert_eq!(stats.samples, 5);
+        assert_eq!(stats.outliers, 0);
+    }
 }
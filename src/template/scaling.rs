@@ -0,0 +1,144 @@
+use std::time::Duration;
+
+use crate::template::run_multi::{child_commands, child_time_limit};
+use crate::template::{runner, BenchOptions, Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+
+/// Thread counts a day is benched with by [`run_scaling`]: powers of two up to `max`, and `max` itself.
+pub fn thread_counts(max: usize) -> Vec<usize> {
+    let mut counts: Vec<usize> = std::iter::successors(Some(1_usize), |n| n.checked_mul(2))
+        .take_while(|n| *n < max)
+        .collect();
+    counts.push(max.max(1));
+    counts
+}
+
+/// Benches `day` with each of the [`thread_counts`] up to the size of rayon's default pool
+/// and prints the speedup and efficiency of both parts relative to a single thread.
+/// Returns whether the day failed to run with any thread count.
+pub fn run_scaling(day: Day, bench: BenchOptions, timeout: Option<Duration>) -> bool {
+    // children read the environment themselves, only an explicit timeout has to be passed on.
+    let child_timeout = timeout;
+    let time_limit = timeout
+        .or_else(runner::timeout_from_env)
+        .map(|timeout| child_time_limit(timeout, Some(&bench)));
+
+    let mut parts: [Vec<(usize, Duration)>; 2] = [vec![], vec![]];
+
+    for threads in thread_counts(rayon::current_num_threads()) {
+        let label = if threads == 1 { "thread" } else { "threads" };
+        println!("{ANSI_BOLD}Day {day} with {threads} {label}{ANSI_RESET}");
+        println!("------");
+
+        let output = child_commands::run_solution(
+            day,
+            Some(&bench),
+            true,
+            child_timeout,
+            time_limit,
+            false,
+            Some(threads),
+        )
+        .unwrap();
+
+        let Some(output) = output else {
+            println!("Not solved.");
+            return true;
+        };
+
+        // timings of the remaining thread counts could not be compared to a failed run.
+        if output.timed_out || output.failed {
+            println!("\n{ANSI_BOLD}Failed:{ANSI_RESET} day {day} with {threads} {label}");
+            return true;
+        }
+
+        for report in output
+            .reports
+            .iter()
+            .filter(|report| report.answer.is_some())
+        {
+            let part = match report.name.as_str() {
+                "part_1" => 0,
+                "part_2" => 1,
+                _ => continue,
+            };
+            parts[part].push((threads, report.stats.mean));
+        }
+
+        println!();
+    }
+
+    for (part, rows) in parts.iter().enumerate() {
+        if rows.is_empty() {
+            continue;
+        }
+
+        println!("{ANSI_BOLD}Part {} scaling{ANSI_RESET}", part + 1);
+        format_scaling(rows)
+            .iter()
+            .for_each(|line| println!("{line}"));
+    }
+
+    println!("{ANSI_ITALIC}Efficiency is the speedup per thread.{ANSI_RESET}");
+    false
+}
+
+/// Formats the mean time of a part per thread count, with the speedup and efficiency relative to the first row.
+fn format_scaling(rows: &[(usize, Duration)]) -> Vec<String> {
+    let Some((_, baseline)) = rows.first() else {
+        return vec![];
+    };
+
+    let times: Vec<String> = rows.iter().map(|(_, time)| format!("{time:.1?}")).collect();
+    let time_width = times
+        .iter()
+        .map(|time| time.chars().count())
+        .chain([4])
+        .max()
+        .unwrap_or_default();
+
+    let header = format!("  threads  {:>time_width$}  speedup  efficiency", "time");
+
+    let lines = rows.iter().zip(times).map(|((threads, time), time_str)| {
+        let speedup = baseline.as_secs_f64() / time.as_secs_f64().max(f64::MIN_POSITIVE);
+        #[allow(clippy::cast_precision_loss)]
+        let efficiency = speedup / *threads as f64 * 100.0;
+        format!("  {threads:>7}  {time_str:>time_width$}  {speedup:>6.2}x  {efficiency:>9.0}%")
+    });
+
+    [header].into_iter().chain(lines).collect()
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{format_scaling, thread_counts};
+    use std::time::Duration;
+
+    #[test]
+    fn derives_thread_counts() {
+        assert_eq!(thread_counts(1), vec![1]);
+        assert_eq!(thread_counts(4), vec![1, 2, 4]);
+        assert_eq!(thread_counts(6), vec![1, 2, 4, 6]);
+        assert_eq!(thread_counts(0), vec![1]);
+    }
+
+    #[test]
+    fn formats_scaling() {
+        let lines = format_scaling(&[
+            (1, Duration::from_millis(8)),
+            (2, Duration::from_millis(4)),
+            (4, Duration::from_micros(2500)),
+        ]);
+        assert_eq!(
+            lines,
+            vec![
+                "  threads   time  speedup  efficiency",
+                "        1  8.0ms    1.00x        100%",
+                "        2  4.0ms    2.00x        100%",
+                "        4  2.5ms    3.20x         80%",
+            ]
+        );
+        assert!(format_scaling(&[]).is_empty());
+    }
+}
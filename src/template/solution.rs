@@ -0,0 +1,43 @@
+use crate::template::Answer;
+
+/// A solution that parses its input once and shares the parsed input between both parts.
+/// This way parsing is timed separately from solving and isn't repeated for each part.
+///
+/// Pass the implementing type to the [`solution!`](crate::solution) macro to run it:
+///
+/// ```ignore
+/// advent_of_code::solution!(1, Day01);
+///
+/// pub struct Day01;
+///
+/// impl Solution for Day01 {
+///     type Input = Vec<u32>;
+///     type Output1 = u32;
+///     type Output2 = u32;
+///
+///     fn parse(input: &str) -> Self::Input {
+///         input.lines().map(|l| l.parse().unwrap()).collect()
+///     }
+///
+///     fn part_one(input: &Self::Input) -> Option<Self::Output1> {
+///         input.iter().max().copied()
+///     }
+///
+///     fn part_two(input: &Self::Input) -> Option<Self::Output2> {
+///         Some(input.iter().sum())
+///     }
+/// }
+/// ```
+pub trait Solution {
+    /// The parsed puzzle input, shared by both parts.
+    /// Parts may run on a worker thread to enforce the runner's timeout, hence the `Send + Sync` bounds.
+    type Input: Send + Sync;
+    type Output1: Into<Answer> + Send;
+    type Output2: Into<Answer> + Send;
+
+    fn parse(input: &str) -> Self::Input;
+
+    fn part_one(input: &Self::Input) -> Option<Self::Output1>;
+
+    fn part_two(input: &Self::Input) -> Option<Self::Output2>;
+}
@@ -0,0 +1,437 @@
+/// Answers submitted via aoc-cli and the verdicts they received, stored in `data/<year>/submissions/DD.json`.
+/// They are consulted before every submission, so a known-wrong answer isn't sent twice and cooldowns are respected.
+use std::{
+    collections::HashMap,
+    fs,
+    io::Error,
+    path::PathBuf,
+    str::FromStr,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+use tinyjson::JsonValue;
+
+use crate::template::{layout, Day};
+
+/// The response of the puzzle page to a submitted answer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    /// Wrong, without a hint in which direction.
+    Incorrect,
+    /// The answer was submitted too recently after the last one and hasn't been checked.
+    Wait,
+    /// A response that isn't recognized, e.g. for a part that was already solved.
+    Unknown,
+}
+
+impl Verdict {
+    /// Parses the verdict from the text of the response printed by aoc-cli.
+    pub fn from_response(response: &str) -> Self {
+        let response = normalize(response);
+
+        if response.contains("That's the right answer") {
+            Verdict::Correct
+        } else if response.contains("answer is too high") {
+            Verdict::TooHigh
+        } else if response.contains("answer is too low") {
+            Verdict::TooLow
+        } else if response.contains("That's not the right answer") {
+            Verdict::Incorrect
+        } else if response.contains("You gave an answer too recently") {
+            Verdict::Wait
+        } else {
+            Verdict::Unknown
+        }
+    }
+
+    pub fn is_wrong(self) -> bool {
+        matches!(
+            self,
+            Verdict::TooHigh | Verdict::TooLow | Verdict::Incorrect
+        )
+    }
+
+    fn key(self) -> &'static str {
+        match self {
+            Verdict::Correct => "correct",
+            Verdict::TooHigh => "too_high",
+            Verdict::TooLow => "too_low",
+            Verdict::Incorrect => "incorrect",
+            Verdict::Wait => "wait",
+            Verdict::Unknown => "unknown",
+        }
+    }
+}
+
+/// Collapses the line breaks aoc-cli wraps the response with.
+fn normalize(response: &str) -> String {
+    response.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Parses how long to wait before the next submission, from either
+/// `Please wait 5 minutes before trying again.` or `You have 1m 23s left to wait.`
+pub fn parse_cooldown(response: &str) -> Option<Duration> {
+    let response = normalize(response);
+
+    if let Some(start) = response.find("You have ") {
+        let rest = &response[start + "You have ".len()..];
+        let end = rest.find(" left to wait")?;
+
+        return rest[..end]
+            .split(' ')
+            .map(|part| {
+                let split = part.find(|c: char| !c.is_ascii_digit())?;
+                let (value, unit) = part.split_at(split);
+                let value: u64 = value.parse().ok()?;
+                match unit {
+                    "h" => Some(value * 3600),
+                    "m" => Some(value * 60),
+                    "s" => Some(value),
+                    _ => None,
+                }
+            })
+            .sum::<Option<u64>>()
+            .map(Duration::from_secs);
+    }
+
+    let start = response.to_lowercase().find("please wait ")?;
+    let mut words = response[start + "please wait ".len()..].split(' ');
+    let value = match words.next()? {
+        "one" => 1,
+        "two" => 2,
+        "five" => 5,
+        "ten" => 10,
+        n => n.parse().ok()?,
+    };
+
+    match words.next()?.trim_end_matches('.') {
+        "second" | "seconds" => Some(Duration::from_secs(value)),
+        "minute" | "minutes" => Some(Duration::from_secs(value * 60)),
+        _ => None,
+    }
+}
+
+/// A single answer sent to the puzzle page.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Submission {
+    pub part: u8,
+    pub answer: String,
+    pub verdict: Verdict,
+    /// Time of the submission, in seconds since the unix epoch.
+    pub timestamp: u64,
+    /// Time to wait after this submission before submitting again, if the response asked for it.
+    pub cooldown: Option<Duration>,
+}
+
+/// All submissions for a single day, oldest first.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Submissions {
+    pub submissions: Vec<Submission>,
+}
+
+/// Seconds since the unix epoch.
+pub fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
+impl Submissions {
+    fn path(day: Day) -> PathBuf {
+        layout::data_path(&format!("submissions/{day}.json"))
+    }
+
+    /// Rehydrate the submissions of `day`. If not present, returns no submissions.
+    pub fn read(day: Day) -> Self {
+        fs::read_to_string(Self::path(day))
+            .map_err(|x| x.to_string())
+            .and_then(Submissions::try_from)
+            .unwrap_or_default()
+    }
+
+    /// Dehydrate the submissions of `day` to its JSON file.
+    pub fn store(&self, day: Day) -> Result<(), Error> {
+        let path = Self::path(day);
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let json = JsonValue::from(self.clone());
+        let mut file = fs::File::create(path)?;
+        json.format_to(&mut file)
+    }
+
+    pub fn record(&mut self, submission: Submission) {
+        self.submissions.push(submission);
+    }
+
+    /// Checks whether `answer` may be submitted for `part` at `now`, returns the reason if not.
+    pub fn check(&self, part: u8, answer: &str, now: u64) -> Result<(), String> {
+        let part_submissions = self.submissions.iter().filter(|s| s.part == part);
+
+        for submission in part_submissions {
+            if submission.verdict == Verdict::Correct {
+                return Err(format!(
+                    "part {part} was already solved with {}.",
+                    submission.answer
+                ));
+            }
+
+            if submission.verdict.is_wrong() && submission.answer == answer {
+                return Err(format!("{answer} was already submitted and is wrong."));
+            }
+
+            // a wrong numeric answer bounds the range of possible answers.
+            let bound = submission.answer.parse::<i128>().ok();
+            let value = answer.parse::<i128>().ok();
+            if let (Some(bound), Some(value)) = (bound, value) {
+                if submission.verdict == Verdict::TooHigh && value >= bound {
+                    return Err(format!("{bound} was already too high."));
+                }
+                if submission.verdict == Verdict::TooLow && value <= bound {
+                    return Err(format!("{bound} was already too low."));
+                }
+            }
+        }
+
+        // cooldowns apply to both parts of a day.
+        let wait_until = self
+            .submissions
+            .iter()
+            .filter_map(|s| Some(s.timestamp + s.cooldown?.as_secs()))
+            .max();
+
+        match wait_until {
+            Some(wait_until) if now < wait_until => Err(format!(
+                "the puzzle page asked to wait another {}s before submitting again.",
+                wait_until - now
+            )),
+            _ => Ok(()),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<Submissions> for JsonValue {
+    #[allow(clippy::cast_precision_loss)]
+    fn from(value: Submissions) -> Self {
+        let submissions = value
+            .submissions
+            .iter()
+            .map(|submission| {
+                let mut map: HashMap<String, JsonValue> = HashMap::new();
+                map.insert("part".into(), JsonValue::Number(submission.part.into()));
+                map.insert(
+                    "answer".into(),
+                    JsonValue::String(submission.answer.clone()),
+                );
+                map.insert(
+                    "verdict".into(),
+                    JsonValue::String(submission.verdict.key().into()),
+                );
+                map.insert(
+                    "timestamp".into(),
+                    JsonValue::Number(submission.timestamp as f64),
+                );
+                map.insert(
+                    "cooldown".into(),
+                    match submission.cooldown {
+                        Some(cooldown) => JsonValue::Number(cooldown.as_secs() as f64),
+                        None => JsonValue::Null,
+                    },
+                );
+                JsonValue::Object(map)
+            })
+            .collect();
+
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+        map.insert("submissions".into(), JsonValue::Array(submissions));
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Submission {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected submission to be a JSON object.")?;
+
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>().copied())
+                .ok_or(format!("Expected submission.{key} to be a number."))
+        };
+
+        let string = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<String>().cloned())
+                .ok_or(format!("Expected submission.{key} to be a string."))
+        };
+
+        let verdict = match string("verdict")?.as_str() {
+            "correct" => Verdict::Correct,
+            "too_high" => Verdict::TooHigh,
+            "too_low" => Verdict::TooLow,
+            "incorrect" => Verdict::Incorrect,
+            "wait" => Verdict::Wait,
+            "unknown" => Verdict::Unknown,
+            verdict => return Err(format!("Unknown submission.verdict: {verdict}.")),
+        };
+
+        Ok(Submission {
+            part: number("part")? as u8,
+            answer: string("answer")?,
+            verdict,
+            timestamp: number("timestamp")? as u64,
+            cooldown: match json.get("cooldown") {
+                Some(v) if !v.is_null() => Some(Duration::from_secs(number("cooldown")? as u64)),
+                _ => None,
+            },
+        })
+    }
+}
+
+impl TryFrom<String> for Submissions {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).map_err(|x| x.to_string())?;
+        let submissions = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected submissions to be a JSON object.")?
+            .get("submissions")
+            .and_then(|v| v.get::<Vec<JsonValue>>())
+            .ok_or("Expected submissions.submissions to be an array.")?;
+
+        Ok(Submissions {
+            submissions: submissions
+                .iter()
+                .map(Submission::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{parse_cooldown, Submission, Submissions, Verdict};
+    use std::time::Duration;
+    use tinyjson::JsonValue;
+
+    fn submission(part: u8, answer: &str, verdict: Verdict, timestamp: u64) -> Submission {
+        Submission {
+            part,
+            answer: answer.into(),
+            verdict,
+            timestamp,
+            cooldown: None,
+        }
+    }
+
+    #[test]
+    fn parses_verdicts() {
+        assert_eq!(
+            Verdict::from_response("That's the right answer! You are one gold star closer."),
+            Verdict::Correct
+        );
+        assert_eq!(
+            Verdict::from_response(
+                "That's not the right answer; your answer is too\nhigh. Please wait one minute before trying again."
+            ),
+            Verdict::TooHigh
+        );
+        assert_eq!(
+            Verdict::from_response("That's not the right answer; your answer is too low."),
+            Verdict::TooLow
+        );
+        assert_eq!(
+            Verdict::from_response("That's not the right answer. If you're stuck, ..."),
+            Verdict::Incorrect
+        );
+        assert_eq!(
+            Verdict::from_response("You gave an answer too recently; you have to wait after submitting an answer before trying again. You have 34s left to wait."),
+            Verdict::Wait
+        );
+        assert_eq!(
+            Verdict::from_response("You don't seem to be solving the right level."),
+            Verdict::Unknown
+        );
+    }
+
+    #[test]
+    fn parses_cooldowns() {
+        assert_eq!(
+            parse_cooldown("Please wait one minute before trying again."),
+            Some(Duration::from_secs(60))
+        );
+        assert_eq!(
+            parse_cooldown("please wait 5 minutes before trying again."),
+            Some(Duration::from_secs(300))
+        );
+        assert_eq!(
+            parse_cooldown("You have 1m\n23s left to wait."),
+            Some(Duration::from_secs(83))
+        );
+        assert_eq!(
+            parse_cooldown("You have 34s left to wait."),
+            Some(Duration::from_secs(34))
+        );
+        assert_eq!(parse_cooldown("That's the right answer!"), None);
+    }
+
+    #[test]
+    fn refuses_known_wrong_answers() {
+        let submissions = Submissions {
+            submissions: vec![
+                submission(1, "abc", Verdict::Incorrect, 0),
+                submission(1, "100", Verdict::TooHigh, 0),
+                submission(1, "10", Verdict::TooLow, 0),
+                submission(2, "7", Verdict::Correct, 0),
+            ],
+        };
+
+        assert!(submissions.check(1, "abc", 1000).is_err());
+        assert!(submissions.check(1, "100", 1000).is_err());
+        assert!(submissions.check(1, "150", 1000).is_err());
+        assert!(submissions.check(1, "10", 1000).is_err());
+        assert!(submissions.check(1, "5", 1000).is_err());
+        assert_eq!(submissions.check(1, "50", 1000), Ok(()));
+        assert_eq!(submissions.check(1, "xyz", 1000), Ok(()));
+        assert!(submissions.check(2, "8", 1000).is_err());
+    }
+
+    #[test]
+    fn enforces_cooldowns() {
+        let mut wrong = submission(1, "42", Verdict::TooLow, 1000);
+        wrong.cooldown = Some(Duration::from_secs(60));
+        let submissions = Submissions {
+            submissions: vec![wrong],
+        };
+
+        assert!(submissions.check(2, "1", 1059).is_err());
+        assert_eq!(submissions.check(2, "1", 1060), Ok(()));
+    }
+
+    #[test]
+    fn roundtrips_submissions() {
+        let mut wait = submission(2, "multi\nline", Verdict::Wait, 1_700_000_000);
+        wait.cooldown = Some(Duration::from_secs(34));
+        let submissions = Submissions {
+            submissions: vec![submission(1, "42", Verdict::TooHigh, 1_700_000_000), wait],
+        };
+
+        let json = JsonValue::from(submissions.clone()).stringify().unwrap();
+        assert_eq!(Submissions::try_from(json), Ok(submissions));
+        assert!(
+            Submissions::try_from(r#"{ "submissions": [{ "part": 1 }] }"#.to_string()).is_err()
+        );
+    }
+}
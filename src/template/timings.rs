@@ -1,17 +1,40 @@
-use std::{collections::HashMap, fs, io::Error, str::FromStr};
+use std::{collections::HashMap, fs, io::Error, path::PathBuf, str::FromStr};
 use tinyjson::JsonValue;
 
-use crate::template::Day;
-
-static TIMINGS_FILE_PATH: &str = "./data/timings.json";
+use crate::template::{layout, memory::AllocStats, runner::BenchStats, BenchOptions, Day};
 
 /// Represents benchmark times for a single day.
 #[derive(Clone, Debug)]
 pub struct Timing {
     pub day: Day,
+    /// Parse time of solutions implementing [`Solution`](crate::template::Solution).
+    pub parse: Option<String>,
     pub part_1: Option<String>,
     pub part_2: Option<String>,
     pub total_nanos: f64,
+    /// Allocations of each phase, if counted with `--mem`.
+    pub parse_memory: Option<AllocStats>,
+    pub part_1_memory: Option<AllocStats>,
+    pub part_2_memory: Option<AllocStats>,
+    /// Summary statistics of each phase, stored without the recorded samples.
+    pub parse_stats: Option<BenchStats>,
+    pub part_1_stats: Option<BenchStats>,
+    pub part_2_stats: Option<BenchStats>,
+    /// Settings the day was benched with.
+    pub bench: Option<BenchOptions>,
+    /// Size of the rayon thread pool the day was benched with.
+    pub threads: Option<usize>,
+}
+
+impl Timing {
+    /// The highest peak memory of all phases, if allocations were counted.
+    pub fn peak_bytes(&self) -> Option<usize> {
+        [&self.parse_memory, &self.part_1_memory, &self.part_2_memory]
+            .into_iter()
+            .flatten()
+            .map(|memory| memory.peak_bytes)
+            .max()
+    }
 }
 
 /// Represents benchmark times for a set of days.
@@ -22,16 +45,25 @@ pub struct Timings {
 }
 
 impl Timings {
+    /// The JSON file of the timings of the selected year, `data/<year>/timings.json`.
+    fn path() -> PathBuf {
+        layout::data_path("timings.json")
+    }
+
     /// Dehydrate timings to a JSON file.
     pub fn store_file(&self) -> Result<(), Error> {
+        let path = Self::path();
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
         let json = JsonValue::from(self.clone());
-        let mut file = fs::File::create(TIMINGS_FILE_PATH)?;
+        let mut file = fs::File::create(path)?;
         json.format_to(&mut file)
     }
 
     /// Rehydrate timings from a JSON file. If not present, returns empty timings.
     pub fn read_from_file() -> Self {
-        fs::read_to_string(TIMINGS_FILE_PATH)
+        fs::read_to_string(Self::path())
             .map_err(|x| x.to_string())
             .and_then(Timings::try_from)
             .unwrap_or_default()
@@ -51,7 +83,7 @@ impl Timings {
             }
         }
 
-        data.sort_unstable_by(|a, b| a.day.cmp(&b.day));
+        data.sort_unstable_by_key(|a| a.day);
         Timings { data }
     }
 
@@ -114,6 +146,10 @@ impl From<&Timing> for JsonValue {
         map.insert("day".into(), JsonValue::String(value.day.to_string()));
         map.insert("total_nanos".into(), JsonValue::Number(value.total_nanos));
 
+        if let Some(parse) = &value.parse {
+            map.insert("parse".into(), JsonValue::String(parse.clone()));
+        }
+
         let part_1 = value.part_1.clone().map(JsonValue::String);
         let part_2 = value.part_2.clone().map(JsonValue::String);
 
@@ -133,6 +169,34 @@ impl From<&Timing> for JsonValue {
             },
         );
 
+        for (key, memory) in [
+            ("parse_memory", &value.parse_memory),
+            ("part_1_memory", &value.part_1_memory),
+            ("part_2_memory", &value.part_2_memory),
+        ] {
+            if let Some(memory) = memory {
+                map.insert(key.into(), JsonValue::from(memory));
+            }
+        }
+
+        for (key, stats) in [
+            ("parse_stats", &value.parse_stats),
+            ("part_1_stats", &value.part_1_stats),
+            ("part_2_stats", &value.part_2_stats),
+        ] {
+            if let Some(stats) = stats {
+                map.insert(key.into(), JsonValue::from(stats));
+            }
+        }
+
+        if let Some(bench) = &value.bench {
+            map.insert("bench".into(), JsonValue::from(bench));
+        }
+
+        if let Some(threads) = value.threads {
+            map.insert("threads".into(), JsonValue::Number(threads as f64));
+        }
+
         JsonValue::Object(map)
     }
 }
@@ -151,6 +215,12 @@ impl TryFrom<&JsonValue> for Timing {
             .and_then(|day| Day::from_str(day).ok())
             .ok_or("Expected timing.day to be a Day struct.")?;
 
+        // NOTE: optional, timings stored before parse times were tracked don't have this key.
+        let parse = json
+            .get("parse")
+            .map(|v| if v.is_null() { None } else { v.get::<String>() })
+            .unwrap_or_default();
+
         let part_1 = json
             .get("part_1")
             .map(|v| if v.is_null() { None } else { v.get::<String>() })
@@ -166,11 +236,54 @@ impl TryFrom<&JsonValue> for Timing {
             .and_then(|v| v.get::<f64>().copied())
             .ok_or("Expected timing.total_nanos to be a number.")?;
 
+        // NOTE: optional, memory is only tracked with `--mem`.
+        let memory = |key: &str| {
+            json.get(key)
+                .filter(|v| !v.is_null())
+                .map(AllocStats::try_from)
+                .transpose()
+        };
+
+        // NOTE: optional, timings stored before the statistics were recorded don't have these keys.
+        let stats = |key: &str| {
+            json.get(key)
+                .filter(|v| !v.is_null())
+                .map(BenchStats::try_from)
+                .transpose()
+        };
+
+        // NOTE: optional, timings stored before the bench settings were recorded don't have this key.
+        let bench = json
+            .get("bench")
+            .filter(|v| !v.is_null())
+            .map(BenchOptions::try_from)
+            .transpose()?;
+
+        // NOTE: optional, timings stored before the thread count was recorded don't have this key.
+        let threads = json
+            .get("threads")
+            .filter(|v| !v.is_null())
+            .map(|v| {
+                v.get::<f64>()
+                    .map(|n| *n as usize)
+                    .ok_or("Expected timing.threads to be a number.")
+            })
+            .transpose()?;
+
         Ok(Timing {
             day,
+            parse: parse.cloned(),
             part_1: part_1.cloned(),
             part_2: part_2.cloned(),
             total_nanos,
+            parse_memory: memory("parse_memory")?,
+            part_1_memory: memory("part_1_memory")?,
+            part_2_memory: memory("part_2_memory")?,
+            parse_stats: stats("parse_stats")?,
+            part_1_stats: stats("part_1_stats")?,
+            part_2_stats: stats("part_2_stats")?,
+            bench,
+            threads,
         })
     }
 }
@@ -188,28 +301,60 @@ mod tests {
             data: vec![
                 Timing {
                     day: day!(1),
+                    parse: None,
                     part_1: Some("10ms".into()),
                     part_2: Some("20ms".into()),
                     total_nanos: 3e+10,
+                    parse_memory: None,
+                    part_1_memory: None,
+                    part_2_memory: None,
+                    parse_stats: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    bench: None,
+                    threads: None,
                 },
                 Timing {
                     day: day!(2),
+                    parse: None,
                     part_1: Some("30ms".into()),
                     part_2: Some("40ms".into()),
                     total_nanos: 7e+10,
+                    parse_memory: None,
+                    part_1_memory: None,
+                    part_2_memory: None,
+                    parse_stats: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    bench: None,
+                    threads: None,
                 },
                 Timing {
                     day: day!(4),
+                    parse: None,
                     part_1: Some("40ms".into()),
                     part_2: None,
                     total_nanos: 4e+10,
+                    parse_memory: None,
+                    part_1_memory: None,
+                    part_2_memory: None,
+                    parse_stats: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    bench: None,
+                    threads: None,
                 },
             ],
         }
     }
 
     mod deserialization {
-        use crate::{day, template::timings::Timings};
+        use crate::{
+            day,
+            template::{memory::AllocStats, timings::Timings, BenchOptions},
+        };
+        use std::time::Duration;
+        use tinyjson::JsonValue;
 
         #[test]
         fn handles_json_timings() {
@@ -223,6 +368,77 @@ mod tests {
             assert_eq!(timing.total_nanos, 1_000_000_000_f64);
         }
 
+        #[test]
+        fn handles_json_timings_with_parse() {
+            let json = r#"{ "data": [{ "day": "01", "parse": "5µs", "part_1": "1ms", "part_2": "2ms", "total_nanos": 3005000 }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let timing = timings.data.first().unwrap();
+            assert_eq!(timing.parse, Some("5µs".to_string()));
+            assert_eq!(timing.part_2, Some("2ms".to_string()));
+        }
+
+        #[test]
+        fn handles_json_timings_with_bench_settings() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": "1ms", "part_2": "2ms", "total_nanos": 3000000, "bench": { "time": "0.5s", "min_samples": 20, "max_samples": 500, "warmup": null, "filter_outliers": true } }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let timing = timings.data.first().unwrap();
+            assert_eq!(
+                timing.bench,
+                Some(BenchOptions {
+                    time: Duration::from_millis(500),
+                    min_samples: 20,
+                    max_samples: 500,
+                    warmup: None,
+                    filter_outliers: true,
+                })
+            );
+        }
+
+        #[test]
+        fn handles_json_timings_with_threads() {
+            let json = r#"{ "data": [{ "day": "07", "part_1": "1ms", "part_2": "2ms", "total_nanos": 3000000, "threads": 8 }, { "day": "08", "part_1": "1ms", "part_2": "2ms", "total_nanos": 3000000 }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            assert_eq!(timings.data[0].threads, Some(8));
+            assert_eq!(timings.data[1].threads, None);
+        }
+
+        #[test]
+        fn handles_json_timings_with_memory() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": "1ms", "part_2": "2ms", "total_nanos": 3000000, "part_2_memory": { "peak_bytes": 1024, "allocations": 3, "bytes": 4096 } }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let timing = timings.data.first().unwrap();
+            assert_eq!(timing.part_1_memory, None);
+            assert_eq!(
+                timing.part_2_memory,
+                Some(AllocStats {
+                    peak_bytes: 1024,
+                    allocations: 3,
+                    bytes: 4096
+                })
+            );
+            assert_eq!(timing.peak_bytes(), Some(1024));
+        }
+
+        #[test]
+        fn handles_json_timings_with_stats() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": "1ms", "part_2": "2ms", "total_nanos": 3000000, "part_1_stats": { "samples": 2, "outliers": 0, "min": 900000, "median": 950000, "mean": 950000, "p95": 1000000, "std_dev": 50000 } }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let timing = timings.data.first().unwrap();
+            let stats = timing.part_1_stats.as_ref().unwrap();
+            assert_eq!(stats.samples, 2);
+            assert_eq!(stats.p95, Duration::from_millis(1));
+            assert!(stats.recorded.is_empty());
+            assert!(timing.part_2_stats.is_none());
+
+            let stored = JsonValue::from(timings.clone()).stringify().unwrap();
+            assert!(!stored.contains("recorded"));
+            let restored = Timings::try_from(stored).unwrap();
+            assert_eq!(restored.data[0].part_1_stats, timing.part_1_stats);
+
+            let invalid = r#"{ "data": [{ "day": "01", "part_1": "1ms", "part_2": "2ms", "total_nanos": 3000000, "part_1_stats": { "samples": 2 } }] }"#.to_string();
+            assert!(Timings::try_from(invalid).is_err());
+        }
+
         #[test]
         fn handles_empty_timings() {
             let json = r#"{ "data": [] }"#.to_string();
@@ -279,13 +495,22 @@ mod tests {
             let timings = Timings {
                 data: vec![Timing {
                     day: day!(1),
+                    parse: None,
                     part_1: Some("1ms".into()),
                     part_2: Some("2ms".into()),
                     total_nanos: 3_000_000_000_f64,
+                    parse_memory: None,
+                    part_1_memory: None,
+                    part_2_memory: None,
+                    parse_stats: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    bench: None,
+                    threads: None,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), true);
+            assert_eq!(timings.is_day_complete(day!(1)), true);
         }
 
         #[test]
@@ -293,13 +518,22 @@ mod tests {
             let timings = Timings {
                 data: vec![Timing {
                     day: day!(1),
+                    parse: None,
                     part_1: Some("1ms".into()),
                     part_2: None,
                     total_nanos: 1_000_000_000_f64,
+                    parse_memory: None,
+                    part_1_memory: None,
+                    part_2_memory: None,
+                    parse_stats: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    bench: None,
+                    threads: None,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
         }
 
         #[test]
@@ -307,13 +541,22 @@ mod tests {
             let timings = Timings {
                 data: vec![Timing {
                     day: day!(1),
+                    parse: None,
                     part_1: None,
                     part_2: None,
                     total_nanos: 0.0,
+                    parse_memory: None,
+                    part_1_memory: None,
+                    part_2_memory: None,
+                    parse_stats: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    bench: None,
+                    threads: None,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
         }
     }
 
@@ -331,9 +574,18 @@ mod tests {
             let other = Timings {
                 data: vec![Timing {
                     day: day!(3),
+                    parse: None,
                     part_1: None,
                     part_2: None,
                     total_nanos: 0_f64,
+                    parse_memory: None,
+                    part_1_memory: None,
+                    part_2_memory: None,
+                    parse_stats: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    bench: None,
+                    threads: None,
                 }],
             };
             let merged = timings.merge(&other);
@@ -351,9 +603,18 @@ mod tests {
             let other = Timings {
                 data: vec![Timing {
                     day: day!(2),
+                    parse: None,
                     part_1: None,
                     part_2: None,
                     total_nanos: 0_f64,
+                    parse_memory: None,
+                    part_1_memory: None,
+                    part_2_memory: None,
+                    parse_stats: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    bench: None,
+                    threads: None,
                 }],
             };
             let merged = timings.merge(&other);
@@ -0,0 +1,333 @@
+//! When puzzles unlock: at midnight in the timezone of the server (UTC-5), from the 1st of december
+//! to the [last day](Year::last_day) of the year.
+//!
+//! The time is read from a [`Clock`], so the scheduling can be tested with a clock that doesn't wait.
+use std::time::Duration;
+
+use chrono::{DateTime, Datelike, FixedOffset, TimeZone, Utc};
+
+use crate::template::{Day, Year};
+
+const SERVER_UTC_OFFSET: i32 = -5;
+
+/// Retries of a failed download back off up to this delay.
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(60);
+
+/// A source of time.
+pub trait Clock {
+    fn now(&self) -> DateTime<Utc>;
+    fn sleep(&self, duration: Duration);
+}
+
+/// The clock of the system.
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+
+    fn sleep(&self, duration: Duration) {
+        std::thread::sleep(duration);
+    }
+}
+
+/// The unlock of the puzzle of `day` in `year`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Unlock {
+    pub year: Year,
+    pub day: Day,
+    pub at: DateTime<Utc>,
+}
+
+fn server_offset() -> FixedOffset {
+    FixedOffset::east_opt(SERVER_UTC_OFFSET * 3600).expect("offset is in range")
+}
+
+/// The puzzle of the day at `now`, [`None`] outside of the days of advent.
+pub fn current(now: DateTime<Utc>) -> Option<(Year, Day)> {
+    let today = now.with_timezone(&server_offset());
+    let year = Year::new(u16::try_from(today.year()).ok()?)?;
+
+    if today.month() == 12 && today.day() <= u32::from(year.last_day().into_inner()) {
+        let day = Day::new(u8::try_from(today.day()).ok()?)?;
+        Some((year, day))
+    } else {
+        None
+    }
+}
+
+/// The first unlock after `now`. A puzzle that unlocks exactly at `now` is already unlocked.
+pub fn next(now: DateTime<Utc>) -> Option<Unlock> {
+    let today = now.with_timezone(&server_offset());
+    let last_day = Year::new(u16::try_from(today.year()).ok()?)?.last_day();
+
+    let (year, day) = match (today.month(), today.day()) {
+        (12, day) if day < u32::from(last_day.into_inner()) => (today.year(), day + 1),
+        (12, _) => (today.year() + 1, 1),
+        _ => (today.year(), 1),
+    };
+
+    let at = server_offset()
+        .with_ymd_and_hms(year, 12, day, 0, 0, 0)
+        .single()?
+        .with_timezone(&Utc);
+
+    Some(Unlock {
+        year: Year::new(u16::try_from(year).ok()?)?,
+        day: Day::new(u8::try_from(day).ok()?)?,
+        at,
+    })
+}
+
+/// Sleeps on `clock` until `at`, calling `on_tick` with the time that is left about once per second.
+/// The ticks after the first fall on whole seconds.
+pub fn wait_until(clock: &impl Clock, at: DateTime<Utc>, mut on_tick: impl FnMut(Duration)) {
+    // NOTE: the time is read again after every sleep, the system may have been suspended meanwhile.
+    while let Ok(left) = (at - clock.now()).to_std() {
+        if left.is_zero() {
+            break;
+        }
+        on_tick(left);
+
+        let to_next_second = match left.subsec_nanos() {
+            0 => Duration::from_secs(1),
+            nanos => Duration::from_nanos(u64::from(nanos)),
+        };
+        clock.sleep(to_next_second.min(left));
+    }
+}
+
+/// Calls `f` up to `attempts` times until it succeeds, sleeping on `clock` between attempts.
+/// The delay starts at one second and doubles after every failed attempt, up to a minute.
+/// `on_error` is called with each error and the delay before the next attempt, [`None`] after the last one.
+pub fn retry<T, E>(
+    clock: &impl Clock,
+    attempts: u32,
+    mut f: impl FnMut() -> Result<T, E>,
+    mut on_error: impl FnMut(&E, Option<Duration>),
+) -> Result<T, E> {
+    let mut delay = Duration::from_secs(1);
+    let mut attempt = 1;
+
+    loop {
+        match f() {
+            Ok(value) => return Ok(value),
+            Err(e) if attempt >= attempts => {
+                on_error(&e, None);
+                return Err(e);
+            }
+            Err(e) => {
+                on_error(&e, Some(delay));
+                clock.sleep(delay);
+                delay = (delay * 2).min(MAX_RETRY_DELAY);
+                attempt += 1;
+            }
+        }
+    }
+}
+
+/// Formats the time left until an unlock, e.g. `2d 03:04:05` or `03:04:05`. Partial seconds count as a full second.
+pub fn format_countdown(left: Duration) -> String {
+    let secs = left.as_secs() + u64::from(left.subsec_nanos() > 0);
+    let (days, hours, minutes, secs) = (secs / 86400, secs / 3600 % 24, secs / 60 % 60, secs % 60);
+
+    if days > 0 {
+        format!("{days}d {hours:02}:{minutes:02}:{secs:02}")
+    } else {
+        format!("{hours:02}:{minutes:02}:{secs:02}")
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{current, format_countdown, next, retry, wait_until, Clock, Unlock};
+    use crate::{day, year};
+    use chrono::{DateTime, TimeZone, Utc};
+    use std::cell::{Cell, RefCell};
+    use std::time::Duration;
+
+    /// A clock that only advances when slept on.
+    struct FakeClock {
+        now: Cell<DateTime<Utc>>,
+        sleeps: RefCell<Vec<Duration>>,
+    }
+
+    impl FakeClock {
+        fn at(now: DateTime<Utc>) -> Self {
+            Self {
+                now: Cell::new(now),
+                sleeps: RefCell::new(vec![]),
+            }
+        }
+    }
+
+    impl Clock for FakeClock {
+        fn now(&self) -> DateTime<Utc> {
+            self.now.get()
+        }
+
+        fn sleep(&self, duration: Duration) {
+            self.sleeps.borrow_mut().push(duration);
+            self.now.set(self.now.get() + duration);
+        }
+    }
+
+    fn utc(year: i32, month: u32, day: u32, hour: u32, min: u32, sec: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(year, month, day, hour, min, sec)
+            .unwrap()
+    }
+
+    #[test]
+    fn finds_current_puzzle() {
+        // still the 30th of november in UTC-5.
+        assert_eq!(current(utc(2024, 12, 1, 4, 59, 59)), None);
+        assert_eq!(
+            current(utc(2024, 12, 1, 5, 0, 0)),
+            Some((year!(2024), day!(1)))
+        );
+        assert_eq!(
+            current(utc(2024, 12, 26, 4, 0, 0)),
+            Some((year!(2024), day!(25)))
+        );
+        assert_eq!(current(utc(2024, 12, 26, 5, 0, 0)), None);
+    }
+
+    #[test]
+    fn finds_current_puzzle_of_short_years() {
+        assert_eq!(
+            current(utc(2025, 12, 13, 4, 59, 59)),
+            Some((year!(2025), day!(12)))
+        );
+        assert_eq!(current(utc(2025, 12, 13, 5, 0, 0)), None);
+        assert_eq!(current(utc(2025, 12, 25, 12, 0, 0)), None);
+    }
+
+    #[test]
+    fn finds_next_unlock() {
+        let unlock = |year, day, at| Some(Unlock { year, day, at });
+
+        assert_eq!(
+            next(utc(2024, 7, 14, 12, 0, 0)),
+            unlock(year!(2024), day!(1), utc(2024, 12, 1, 5, 0, 0))
+        );
+        assert_eq!(
+            next(utc(2024, 12, 1, 4, 59, 59)),
+            unlock(year!(2024), day!(1), utc(2024, 12, 1, 5, 0, 0))
+        );
+        // the puzzle of the 5th is unlocked already.
+        assert_eq!(
+            next(utc(2024, 12, 5, 5, 0, 0)),
+            unlock(year!(2024), day!(6), utc(2024, 12, 6, 5, 0, 0))
+        );
+        assert_eq!(
+            next(utc(2024, 12, 25, 3, 0, 0)),
+            unlock(year!(2024), day!(25), utc(2024, 12, 25, 5, 0, 0))
+        );
+        assert_eq!(
+            next(utc(2024, 12, 25, 5, 0, 0)),
+            unlock(year!(2025), day!(1), utc(2025, 12, 1, 5, 0, 0))
+        );
+    }
+
+    #[test]
+    fn finds_next_unlock_of_short_years() {
+        let unlock = |year, day, at| Some(Unlock { year, day, at });
+
+        assert_eq!(
+            next(utc(2025, 12, 12, 3, 0, 0)),
+            unlock(year!(2025), day!(12), utc(2025, 12, 12, 5, 0, 0))
+        );
+        // the 12th is the last puzzle of 2025.
+        assert_eq!(
+            next(utc(2025, 12, 12, 5, 0, 0)),
+            unlock(year!(2026), day!(1), utc(2026, 12, 1, 5, 0, 0))
+        );
+        assert_eq!(
+            next(utc(2025, 12, 20, 12, 0, 0)),
+            unlock(year!(2026), day!(1), utc(2026, 12, 1, 5, 0, 0))
+        );
+    }
+
+    #[test]
+    fn waits_until_unlock() {
+        let at = utc(2025, 12, 1, 5, 0, 0);
+        let clock = FakeClock::at(at - Duration::from_millis(2500));
+        let mut ticks = vec![];
+
+        wait_until(&clock, at, |left| ticks.push(left));
+
+        assert_eq!(clock.now(), at);
+        assert_eq!(
+            ticks,
+            vec![
+                Duration::from_millis(2500),
+                Duration::from_secs(2),
+                Duration::from_secs(1)
+            ]
+        );
+
+        // nothing to wait for after the unlock.
+        wait_until(&clock, at - Duration::from_secs(1), |_| panic!("ticked"));
+        assert_eq!(clock.sleeps.borrow().len(), 3);
+    }
+
+    #[test]
+    fn retries_with_backoff() {
+        let clock = FakeClock::at(utc(2025, 12, 1, 5, 0, 0));
+        let mut calls = 0;
+        let mut errors = vec![];
+
+        let result = retry(
+            &clock,
+            5,
+            || {
+                calls += 1;
+                if calls < 4 {
+                    Err(calls)
+                } else {
+                    Ok(calls)
+                }
+            },
+            |e, delay| errors.push((*e, delay)),
+        );
+
+        assert_eq!(result, Ok(4));
+        assert_eq!(errors.len(), 3);
+        assert_eq!(
+            *clock.sleeps.borrow(),
+            vec![
+                Duration::from_secs(1),
+                Duration::from_secs(2),
+                Duration::from_secs(4)
+            ]
+        );
+
+        let clock = FakeClock::at(utc(2025, 12, 1, 5, 0, 0));
+        let mut last_error = None;
+        let result: Result<(), _> = retry(
+            &clock,
+            9,
+            || Err("down"),
+            |_, delay| last_error = Some(delay),
+        );
+
+        assert_eq!(result, Err("down"));
+        assert_eq!(last_error, Some(None));
+        assert_eq!(clock.sleeps.borrow().len(), 8);
+        assert_eq!(clock.sleeps.borrow()[7], Duration::from_secs(60));
+    }
+
+    #[test]
+    fn formats_countdown() {
+        assert_eq!(format_countdown(Duration::from_millis(2500)), "00:00:03");
+        assert_eq!(format_countdown(Duration::from_secs(2)), "00:00:02");
+        assert_eq!(format_countdown(Duration::from_secs(3723)), "01:02:03");
+        assert_eq!(
+            format_countdown(Duration::from_secs(2 * 86400 + 3723)),
+            "2d 01:02:03"
+        );
+    }
+}
@@ -0,0 +1,168 @@
+use std::error::Error;
+use std::fmt::Display;
+use std::str::FromStr;
+use std::sync::OnceLock;
+use std::{env, path::Path};
+
+use crate::template::{layout, Day};
+
+/// The first year of advent.
+const FIRST_YEAR: u16 = 2015;
+
+/// The first year of advent with 12 puzzles instead of 25.
+const FIRST_SHORT_YEAR: u16 = 2025;
+
+/// A valid year of advent (i.e. 2015 or later).
+///
+/// # Display
+/// This value displays as a four digit number.
+///
+/// ```
+/// # use advent_of_code::template::Year;
+/// let year = Year::new(2024).unwrap();
+/// assert_eq!(year.to_string(), "2024")
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Year(u16);
+
+impl Year {
+    /// Creates a [`Year`] from the provided value if it's a year of advent,
+    /// returns [`None`] otherwise.
+    pub fn new(year: u16) -> Option<Self> {
+        if !(FIRST_YEAR..=9999).contains(&year) {
+            return None;
+        }
+        Some(Self(year))
+    }
+
+    // Not part of the public API
+    #[doc(hidden)]
+    pub const fn __new_unchecked(year: u16) -> Self {
+        Self(year)
+    }
+
+    /// Converts the [`Year`] into an [`u16`].
+    pub fn into_inner(self) -> u16 {
+        self.0
+    }
+
+    /// The day of the last puzzle: the 25th until 2024, the 12th from 2025 on.
+    pub fn last_day(self) -> Day {
+        if self.0 >= FIRST_SHORT_YEAR {
+            crate::day!(12)
+        } else {
+            crate::day!(25)
+        }
+    }
+
+    /// The year configured as `AOC_YEAR` in `.cargo/config.toml`, if any.
+    /// Files in the layout without years belong to this year, see [`layout`](super::layout).
+    pub fn configured() -> Option<Self> {
+        env::var("AOC_YEAR").ok()?.parse().ok()
+    }
+
+    /// The year this process works on: the year passed to [`Year::select`] (e.g. with `--year`),
+    /// the year in the name of a solution binary like `2023-05`, or the [configured](Year::configured) year.
+    ///
+    /// # Panics
+    /// If no year is selected and `AOC_YEAR` isn't set.
+    pub fn selected() -> Self {
+        *SELECTED.get_or_init(|| {
+            // NOTE: solution binaries and their tests don't parse `--year`, they are named after their year.
+            let exe = env::current_exe().ok();
+            let bin_year = exe
+                .as_deref()
+                .and_then(Path::file_stem)
+                .and_then(|name| layout::year_of_bin(&name.to_string_lossy()));
+
+            bin_year
+                .or_else(Self::configured)
+                .expect("no year selected, pass `--year` or set `AOC_YEAR` in `.cargo/config.toml`")
+        })
+    }
+
+    /// Selects the year this process works on. Only the first call has an effect,
+    /// and only before the [selected](Year::selected) year is first used.
+    pub fn select(year: Self) {
+        let _ = SELECTED.set(year);
+    }
+}
+
+static SELECTED: OnceLock<Year> = OnceLock::new();
+
+impl Display for Year {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl PartialEq<u16> for Year {
+    fn eq(&self, other: &u16) -> bool {
+        self.0.eq(other)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl FromStr for Year {
+    type Err = YearFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let year = s.parse().map_err(|_| YearFromStrError)?;
+        Self::new(year).ok_or(YearFromStrError)
+    }
+}
+
+/// An error which can be returned when parsing a [`Year`].
+#[derive(Debug)]
+pub struct YearFromStrError;
+
+impl Error for YearFromStrError {}
+
+impl Display for YearFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("expecting a year of advent, 2015 or later")
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Creates a [`Year`] value in a const context.
+#[macro_export]
+macro_rules! year {
+    ($year:expr) => {{
+        const _ASSERT: () = assert!(
+            $year >= 2015 && $year <= 9999,
+            concat!(
+                "invalid year `",
+                $year,
+                "`, expecting a year of advent, 2015 or later"
+            ),
+        );
+        $crate::template::Year::__new_unchecked($year)
+    }};
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Year;
+
+    #[test]
+    fn parses_years() {
+        assert_eq!("2023".parse::<Year>().ok(), Some(Year(2023)));
+        assert_eq!("2015".parse::<Year>().ok(), Some(Year(2015)));
+        assert!("2014".parse::<Year>().is_err());
+        assert!("23".parse::<Year>().is_err());
+        assert!("twenty".parse::<Year>().is_err());
+    }
+
+    #[test]
+    fn finds_last_day() {
+        assert_eq!(Year(2015).last_day(), 25);
+        assert_eq!(Year(2024).last_day(), 25);
+        assert_eq!(Year(2025).last_day(), 12);
+        assert_eq!(Year(2026).last_day(), 12);
+    }
+}
@@ -0,0 +1,459 @@
+//! A counter (multiset) that tracks how many times each key occurs.
+//!
+//! ```
+//!   # use advent_of_code::utils::*;
+//!
+//!   let mut counter: Counter<char> = "abracadabra".chars().collect();
+//!   counter.add('z', 3);
+//!
+//!   assert_eq!(counter.get(&'a'), 5);
+//!   assert_eq!(counter.get(&'z'), 3);
+//!   assert_eq!(counter.most_common(1), vec![(&'a', 5)]);
+//! ```
+//!
+//! For small, dense integer keys (e.g. digits or bytes) [`DenseCounter`] stores the counts
+//! in a `Vec` indexed by the key, which avoids hashing altogether.
+
+use std::cmp::Reverse;
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::ops::{Add, AddAssign, Mul, MulAssign, Sub, SubAssign};
+
+// COUNTER
+#[derive(Debug, Clone)]
+pub struct Counter<K> {
+    counts: HashMap<K, usize>,
+}
+
+impl<K: Hash + Eq> PartialEq for Counter<K> {
+    fn eq(&self, other: &Self) -> bool {
+        self.counts == other.counts
+    }
+}
+
+impl<K: Hash + Eq> Eq for Counter<K> {}
+
+impl<K> Default for Counter<K> {
+    fn default() -> Self {
+        Self {
+            counts: HashMap::new(),
+        }
+    }
+}
+
+impl<K: Hash + Eq> Counter<K> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            counts: HashMap::with_capacity(capacity),
+        }
+    }
+
+    /// Adds `n` occurrences of `key`. Adding zero occurrences doesn't create an entry.
+    #[inline]
+    pub fn add(&mut self, key: K, n: usize) {
+        if n > 0 {
+            *self.counts.entry(key).or_insert(0) += n;
+        }
+    }
+
+    /// Adds a single occurrence of `key`.
+    #[inline]
+    pub fn insert(&mut self, key: K) {
+        self.add(key, 1);
+    }
+
+    /// Removes up to `n` occurrences of `key` and returns how many were actually removed.
+    /// Keys whose count drops to zero are removed from the counter.
+    pub fn remove(&mut self, key: &K, n: usize) -> usize {
+        let Some(count) = self.counts.get_mut(key) else {
+            return 0;
+        };
+
+        let removed = n.min(*count);
+        *count -= removed;
+        if *count == 0 {
+            self.counts.remove(key);
+        }
+
+        removed
+    }
+
+    /// Returns the amount of occurrences of `key`, zero if it was never added.
+    #[inline]
+    pub fn get(&self, key: &K) -> usize {
+        self.counts.get(key).copied().unwrap_or(0)
+    }
+
+    #[inline]
+    pub fn contains(&self, key: &K) -> bool {
+        self.counts.contains_key(key)
+    }
+
+    /// Number of distinct keys.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.counts.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.counts.is_empty()
+    }
+
+    /// Sum of all the counts.
+    pub fn total(&self) -> usize {
+        self.counts.values().sum()
+    }
+
+    /// Iterates over the keys and their counts in arbitrary order.
+    pub fn iter(&self) -> impl Iterator<Item = (&K, usize)> {
+        self.counts.iter().map(|(k, &v)| (k, v))
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item = &K> {
+        self.counts.keys()
+    }
+
+    /// Returns the `n` keys with the highest counts, most common first.
+    /// Keys with equal counts are returned in arbitrary order.
+    pub fn most_common(&self, n: usize) -> Vec<(&K, usize)> {
+        let mut all = self.iter().collect::<Vec<_>>();
+        all.sort_unstable_by_key(|&(_, count)| Reverse(count));
+        all.truncate(n);
+        all
+    }
+
+    /// Multiplies every count by `n`. Scaling by zero empties the counter.
+    pub fn scale(&mut self, n: usize) {
+        if n == 0 {
+            self.counts.clear();
+        } else {
+            self.counts.values_mut().for_each(|v| *v *= n);
+        }
+    }
+}
+
+impl<K: Hash + Eq + Ord> Counter<K> {
+    /// Iterates over the keys and their counts ordered by key.
+    pub fn iter_sorted(&self) -> impl Iterator<Item = (&K, usize)> {
+        let mut all = self.iter().collect::<Vec<_>>();
+        all.sort_unstable_by(|a, b| a.0.cmp(b.0));
+        all.into_iter()
+    }
+}
+
+impl<K: Hash + Eq> FromIterator<K> for Counter<K> {
+    fn from_iter<I: IntoIterator<Item = K>>(iter: I) -> Self {
+        let mut counter = Counter::new();
+        counter.extend(iter);
+        counter
+    }
+}
+
+impl<K: Hash + Eq> Extend<K> for Counter<K> {
+    fn extend<I: IntoIterator<Item = K>>(&mut self, iter: I) {
+        iter.into_iter().for_each(|k| self.insert(k));
+    }
+}
+
+impl<K> IntoIterator for Counter<K> {
+    type Item = (K, usize);
+    type IntoIter = std::collections::hash_map::IntoIter<K, usize>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.counts.into_iter()
+    }
+}
+
+impl<K: Hash + Eq> AddAssign for Counter<K> {
+    fn add_assign(&mut self, rhs: Self) {
+        for (k, v) in rhs {
+            self.add(k, v);
+        }
+    }
+}
+
+impl<K: Hash + Eq> Add for Counter<K> {
+    type Output = Self;
+
+    fn add(mut self, rhs: Self) -> Self::Output {
+        self += rhs;
+        self
+    }
+}
+
+/// Subtraction saturates at zero, keys that end up with a zero count are removed.
+impl<K: Hash + Eq> SubAssign for Counter<K> {
+    fn sub_assign(&mut self, rhs: Self) {
+        for (k, v) in rhs {
+            self.remove(&k, v);
+        }
+    }
+}
+
+impl<K: Hash + Eq> Sub for Counter<K> {
+    type Output = Self;
+
+    fn sub(mut self, rhs: Self) -> Self::Output {
+        self -= rhs;
+        self
+    }
+}
+
+impl<K: Hash + Eq> MulAssign<usize> for Counter<K> {
+    fn mul_assign(&mut self, rhs: usize) {
+        self.scale(rhs);
+    }
+}
+
+impl<K: Hash + Eq> Mul<usize> for Counter<K> {
+    type Output = Self;
+
+    fn mul(mut self, rhs: usize) -> Self::Output {
+        self.scale(rhs);
+        self
+    }
+}
+
+// DENSE COUNTER
+/// A [`Counter`] for small integer keys, backed by a `Vec` indexed by the key.
+/// The backing storage grows to fit the largest key that was added.
+#[derive(Debug, Clone, Default)]
+pub struct DenseCounter {
+    counts: Vec<usize>,
+}
+
+// NOTE: compares the counts only, the backing storage may have trailing zeros.
+impl PartialEq for DenseCounter {
+    fn eq(&self, other: &Self) -> bool {
+        self.iter().eq(other.iter())
+    }
+}
+
+impl Eq for DenseCounter {}
+
+impl DenseCounter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a counter that can hold keys in `0..max_key` without reallocating.
+    pub fn with_max_key(max_key: usize) -> Self {
+        Self {
+            counts: vec![0; max_key],
+        }
+    }
+
+    #[inline]
+    pub fn add(&mut self, key: usize, n: usize) {
+        if n == 0 {
+            return;
+        }
+        if key >= self.counts.len() {
+            self.counts.resize(key + 1, 0);
+        }
+        self.counts[key] += n;
+    }
+
+    #[inline]
+    pub fn insert(&mut self, key: usize) {
+        self.add(key, 1);
+    }
+
+    /// Removes up to `n` occurrences of `key` and returns how many were actually removed.
+    pub fn remove(&mut self, key: usize, n: usize) -> usize {
+        let Some(count) = self.counts.get_mut(key) else {
+            return 0;
+        };
+
+        let removed = n.min(*count);
+        *count -= removed;
+        removed
+    }
+
+    #[inline]
+    pub fn get(&self, key: usize) -> usize {
+        self.counts.get(key).copied().unwrap_or(0)
+    }
+
+    /// Number of distinct keys with a non-zero count.
+    pub fn len(&self) -> usize {
+        self.counts.iter().filter(|&&v| v > 0).count()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.counts.iter().all(|&v| v == 0)
+    }
+
+    pub fn total(&self) -> usize {
+        self.counts.iter().sum()
+    }
+
+    /// Iterates over the keys with a non-zero count, ordered by key.
+    pub fn iter(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.counts
+            .iter()
+            .enumerate()
+            .filter(|(_, &v)| v > 0)
+            .map(|(k, &v)| (k, v))
+    }
+
+    /// Same as [`DenseCounter::iter`], keys are always stored in order.
+    pub fn iter_sorted(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.iter()
+    }
+
+    /// Returns the `n` keys with the highest counts, most common first.
+    /// Keys with equal counts are ordered by key.
+    pub fn most_common(&self, n: usize) -> Vec<(usize, usize)> {
+        let mut all = self.iter().collect::<Vec<_>>();
+        all.sort_by_key(|&(_, count)| Reverse(count));
+        all.truncate(n);
+        all
+    }
+
+    pub fn scale(&mut self, n: usize) {
+        self.counts.iter_mut().for_each(|v| *v *= n);
+    }
+}
+
+impl FromIterator<usize> for DenseCounter {
+    fn from_iter<I: IntoIterator<Item = usize>>(iter: I) -> Self {
+        let mut counter = DenseCounter::new();
+        counter.extend(iter);
+        counter
+    }
+}
+
+impl Extend<usize> for DenseCounter {
+    fn extend<I: IntoIterator<Item = usize>>(&mut self, iter: I) {
+        iter.into_iter().for_each(|k| self.insert(k));
+    }
+}
+
+impl AddAssign for DenseCounter {
+    fn add_assign(&mut self, rhs: Self) {
+        for (k, v) in rhs.iter() {
+            self.add(k, v);
+        }
+    }
+}
+
+impl Add for DenseCounter {
+    type Output = Self;
+
+    fn add(mut self, rhs: Self) -> Self::Output {
+        self += rhs;
+        self
+    }
+}
+
+impl SubAssign for DenseCounter {
+    fn sub_assign(&mut self, rhs: Self) {
+        for (k, v) in rhs.iter() {
+            self.remove(k, v);
+        }
+    }
+}
+
+impl Sub for DenseCounter {
+    type Output = Self;
+
+    fn sub(mut self, rhs: Self) -> Self::Output {
+        self -= rhs;
+        self
+    }
+}
+
+impl MulAssign<usize> for DenseCounter {
+    fn mul_assign(&mut self, rhs: usize) {
+        self.scale(rhs);
+    }
+}
+
+impl Mul<usize> for DenseCounter {
+    type Output = Self;
+
+    fn mul(mut self, rhs: usize) -> Self::Output {
+        self.scale(rhs);
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Counter, DenseCounter};
+
+    #[test]
+    fn counter_working() {
+        let mut counter: Counter<u32> = [3, 4, 2, 1, 3, 3].into_iter().collect();
+        assert_eq!(counter.get(&3), 3);
+        assert_eq!(counter.get(&5), 0);
+        assert_eq!(counter.len(), 4);
+        assert_eq!(counter.total(), 6);
+
+        counter.add(5, 2);
+        counter.add(6, 0);
+        assert_eq!(counter.get(&5), 2);
+        assert!(!counter.contains(&6));
+
+        assert_eq!(counter.remove(&5, 10), 2);
+        assert!(!counter.contains(&5));
+        assert_eq!(counter.remove(&5, 1), 0);
+
+        assert_eq!(counter.most_common(1), vec![(&3, 3)]);
+        let sorted = counter.iter_sorted().collect::<Vec<_>>();
+        assert_eq!(sorted, vec![(&1, 1), (&2, 1), (&3, 3), (&4, 1)]);
+    }
+
+    #[test]
+    fn counter_arithmetic_working() {
+        let a: Counter<char> = "aab".chars().collect();
+        let b: Counter<char> = "abc".chars().collect();
+
+        let sum = a.clone() + b.clone();
+        assert_eq!(sum.get(&'a'), 3);
+        assert_eq!(sum.get(&'b'), 2);
+        assert_eq!(sum.get(&'c'), 1);
+
+        let diff = a.clone() - b;
+        assert_eq!(diff.get(&'a'), 1);
+        assert!(!diff.contains(&'b'));
+        assert!(!diff.contains(&'c'));
+
+        let scaled = a.clone() * 3;
+        assert_eq!(scaled.get(&'a'), 6);
+        assert_eq!(scaled.get(&'b'), 3);
+        let mut emptied = a;
+        emptied.scale(0);
+        assert!(emptied.is_empty());
+    }
+
+    #[test]
+    fn dense_counter_working() {
+        let mut counter: DenseCounter = [0, 5, 5, 2].into_iter().collect();
+        assert_eq!(counter.get(5), 2);
+        assert_eq!(counter.get(100), 0);
+        assert_eq!(counter.len(), 3);
+        assert_eq!(counter.total(), 4);
+        assert_eq!(counter.most_common(2), vec![(5, 2), (0, 1)]);
+
+        counter += [2, 2].into_iter().collect();
+        assert_eq!(counter.get(2), 3);
+        counter -= [5, 5, 5].into_iter().collect();
+        assert_eq!(counter.get(5), 0);
+
+        let counter = counter * 2;
+        assert_eq!(counter.iter().collect::<Vec<_>>(), vec![(0, 2), (2, 6)]);
+
+        let mut empty = DenseCounter::new();
+        empty.add(100, 0);
+        assert_eq!(empty, DenseCounter::with_max_key(10));
+        assert_eq!(counter.clone() - counter.clone(), empty);
+        assert_ne!(counter, empty);
+    }
+}
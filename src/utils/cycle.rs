@@ -0,0 +1,248 @@
+//! Cycle detection for iterated simulations.
+//!
+//! A sequence `x0, f(x0), f(f(x0)), ...` over a finite state space eventually repeats.
+//! The detected [`Cycle`] describes it with `mu`, the index of the first state of the cycle,
+//! and `lambda`, the length of the cycle. This allows jumping to the state after a huge
+//! number of iterations:
+//!
+//! ```
+//!   # use advent_of_code::utils::cycle::*;
+//!
+//!   let step = |x: &u32| (x * x + 1) % 1000;
+//!   let cycle = brent(3, step);
+//!   assert_eq!(cycle, floyd(3, step));
+//!
+//!   let n = 1_000_000_000_000;
+//!   let mut state = 3;
+//!   for _ in 0..cycle.equivalent_step(n) {
+//!       state = step(&state);
+//!   }
+//!   assert_eq!(state, extrapolate(3, step, n));
+//! ```
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+
+/// The states from step `mu` onwards repeat every `lambda` steps.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cycle {
+    pub mu: usize,
+    pub lambda: usize,
+}
+
+impl Cycle {
+    /// Maps step `n` to the earliest step with the same state.
+    #[inline]
+    pub fn equivalent_step(&self, n: usize) -> usize {
+        if n < self.mu {
+            n
+        } else {
+            self.mu + (n - self.mu) % self.lambda
+        }
+    }
+
+    /// Number of steps still needed to reach the state of step `target` when currently at
+    /// step `current`, skipping all the full cycles in between. `current` has to be at least `mu`.
+    #[inline]
+    pub fn remaining_steps(&self, current: usize, target: usize) -> usize {
+        debug_assert!(current >= self.mu, "current step is not inside the cycle");
+        if target <= current {
+            (self.lambda - (current - target) % self.lambda) % self.lambda
+        } else {
+            (target - current) % self.lambda
+        }
+    }
+}
+
+/// Brent's cycle detection. Evaluates `step` fewer times than [`floyd`] and only keeps two states
+/// in memory. Never returns if the sequence doesn't cycle.
+pub fn brent<T: PartialEq + Clone>(x0: T, mut step: impl FnMut(&T) -> T) -> Cycle {
+    // Find the cycle length by searching successive powers of two.
+    let mut power = 1;
+    let mut lambda = 1;
+    let mut tortoise = x0.clone();
+    let mut hare = step(&x0);
+    while tortoise != hare {
+        if power == lambda {
+            tortoise = hare.clone();
+            power *= 2;
+            lambda = 0;
+        }
+        hare = step(&hare);
+        lambda += 1;
+    }
+
+    // Find the start of the cycle with the hare `lambda` steps ahead.
+    let mut tortoise = x0.clone();
+    let mut hare = x0;
+    for _ in 0..lambda {
+        hare = step(&hare);
+    }
+    let mut mu = 0;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        mu += 1;
+    }
+
+    Cycle { mu, lambda }
+}
+
+/// Floyd's "tortoise and hare" cycle detection. Only keeps two states in memory.
+/// Never returns if the sequence doesn't cycle.
+pub fn floyd<T: PartialEq + Clone>(x0: T, mut step: impl FnMut(&T) -> T) -> Cycle {
+    let mut tortoise = step(&x0);
+    let mut hare = step(&tortoise);
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        hare = step(&hare);
+    }
+
+    let mut mu = 0;
+    tortoise = x0;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        mu += 1;
+    }
+
+    let mut lambda = 1;
+    hare = step(&tortoise);
+    while tortoise != hare {
+        hare = step(&hare);
+        lambda += 1;
+    }
+
+    Cycle { mu, lambda }
+}
+
+/// Hash based cycle detection for hand-written simulation loops.
+/// Records the hash of every state together with its step, so a cycle is found as soon as the
+/// first state repeats. States themselves aren't stored.
+#[derive(Debug, Clone, Default)]
+pub struct CycleDetector {
+    seen: HashMap<u64, usize>,
+    step: usize,
+}
+
+impl CycleDetector {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            seen: HashMap::with_capacity(capacity),
+            step: 0,
+        }
+    }
+
+    /// Number of recorded states, which is also the step of the next recorded state.
+    #[inline]
+    pub fn steps(&self) -> usize {
+        self.step
+    }
+
+    /// Records `state` as the state of the current step and advances to the next one.
+    /// Returns the [`Cycle`] once a state has been seen before.
+    pub fn record<S: Hash + ?Sized>(&mut self, state: &S) -> Option<Cycle> {
+        let mut hasher = DefaultHasher::new();
+        state.hash(&mut hasher);
+        let hash = hasher.finish();
+
+        let step = self.step;
+        self.step += 1;
+
+        self.seen.insert(hash, step).map(|mu| Cycle {
+            mu,
+            lambda: step - mu,
+        })
+    }
+
+    pub fn clear(&mut self) {
+        self.seen.clear();
+        self.step = 0;
+    }
+}
+
+/// Returns the state after `n` applications of `step` to `x0`. Once a cycle is detected only the
+/// remainder of the iterations that doesn't make up full cycles is replayed.
+pub fn extrapolate<T: Hash>(x0: T, mut step: impl FnMut(&T) -> T, n: usize) -> T {
+    let mut detector = CycleDetector::new();
+    let mut state = x0;
+
+    for i in 0..n {
+        if let Some(cycle) = detector.record(&state) {
+            for _ in 0..cycle.remaining_steps(i, n) {
+                state = step(&state);
+            }
+            return state;
+        }
+        state = step(&state);
+    }
+
+    state
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn brute_force(x0: u64, step: impl Fn(&u64) -> u64) -> Cycle {
+        let mut history = vec![x0];
+        loop {
+            let next = step(history.last().unwrap());
+            if let Some(mu) = history.iter().position(|&x| x == next) {
+                return Cycle {
+                    mu,
+                    lambda: history.len() - mu,
+                };
+            }
+            history.push(next);
+        }
+    }
+
+    #[test]
+    fn detection_working() {
+        for (x0, m) in [(0, 255), (3, 1000), (7, 10007), (1, 2)] {
+            let step = |x: &u64| (x * x + 1) % m;
+            let expected = brute_force(x0, step);
+            assert_eq!(brent(x0, step), expected);
+            assert_eq!(floyd(x0, step), expected);
+
+            let mut detector = CycleDetector::new();
+            let mut state = x0;
+            let cycle = loop {
+                if let Some(cycle) = detector.record(&state) {
+                    break cycle;
+                }
+                state = step(&state);
+            };
+            assert_eq!(cycle, expected);
+        }
+    }
+
+    #[test]
+    fn extrapolate_working() {
+        let step = |x: &u64| (x * x + 1) % 10007;
+        let naive = |n: usize| (0..n).fold(7, |x, _| step(&x));
+
+        for n in [0, 1, 5, 100, 1000, 12345] {
+            assert_eq!(extrapolate(7, step, n), naive(n));
+        }
+
+        let cycle = brent(7, step);
+        for n in [0, 1, 5, 100, 1000, 12345] {
+            assert_eq!(naive(cycle.equivalent_step(n)), naive(n));
+        }
+    }
+
+    #[test]
+    fn remaining_steps_working() {
+        let cycle = Cycle { mu: 2, lambda: 5 };
+        assert_eq!(cycle.remaining_steps(7, 7), 0);
+        assert_eq!(cycle.remaining_steps(7, 13), 1);
+        assert_eq!(cycle.remaining_steps(7, 4), 2);
+    }
+}
@@ -17,6 +17,7 @@
 use crate::utils::point::*;
 
 use std::collections::HashMap;
+use std::fmt::Display;
 use std::ops::{Index, IndexMut};
 
 // GRID
@@ -167,6 +168,23 @@ impl Grid<u8> {
     }
 }
 
+/// Renders the grid row by row, e.g. to record it as a frame with [`Ctx::frame`](crate::template::Ctx::frame).
+impl Display for Grid<u8> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // `chunks` panics on a chunk size of zero.
+        if self.width == 0 {
+            return Ok(());
+        }
+        for (y, row) in self.field.chunks(self.width as usize).enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            write!(f, "{}", String::from_utf8_lossy(row))?;
+        }
+        Ok(())
+    }
+}
+
 impl<T> Index<Point> for Grid<T> {
     type Output = T;
 
@@ -216,6 +234,13 @@ mod tests {
         assert_eq!(grid[mid_coord], b'^');
     }
 
+    #[test]
+    fn grid_display_working() {
+        let input = "#..\n.#.";
+        assert_eq!(Grid::parse(input).to_string(), input);
+        assert_eq!(Grid::new(0, 3, b'.').to_string(), "");
+    }
+
     #[test]
     fn grid_find_all_working() {
         let input = "89010123
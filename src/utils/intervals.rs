@@ -0,0 +1,314 @@
+//! A set of integers stored as sorted, disjoint, half-open ranges.
+//! Overlapping and adjacent ranges are merged on insert, so the representation is always minimal:
+//!
+//! ```
+//!   # use advent_of_code::utils::*;
+//!
+//!   let mut set: IntervalSet<u32> = [0..3, 5..8].into_iter().collect();
+//!   set.insert(3..5);
+//!   assert_eq!(set.ranges(), &[0..8]);
+//!
+//!   set.remove(2..4);
+//!   assert_eq!(set.ranges(), &[0..2, 4..8]);
+//!   assert_eq!(set.total_len(), 6);
+//!   assert_eq!(set.first_fit(3), Some(4..7));
+//! ```
+use std::ops::Range;
+
+use crate::utils::math::Integer;
+
+// INTERVAL SET
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct IntervalSet<T> {
+    ranges: Vec<Range<T>>,
+}
+
+impl<T: Integer> IntervalSet<T> {
+    pub fn new() -> Self {
+        Self { ranges: Vec::new() }
+    }
+
+    /// The disjoint ranges of this set, ordered by their start.
+    #[inline]
+    pub fn ranges(&self) -> &[Range<T>] {
+        &self.ranges
+    }
+
+    #[inline]
+    pub fn iter(&self) -> std::slice::Iter<'_, Range<T>> {
+        self.ranges.iter()
+    }
+
+    /// Number of disjoint ranges.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.ranges.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// Sum of the lengths of all the ranges.
+    pub fn total_len(&self) -> T {
+        self.ranges
+            .iter()
+            .fold(T::ZERO, |acc, r| acc + (r.end - r.start))
+    }
+
+    /// Index of the first range that ends at or after `value`.
+    #[inline]
+    fn first_ending_from(&self, value: T) -> usize {
+        self.ranges.partition_point(|r| r.end < value)
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        let i = self.ranges.partition_point(|r| r.end <= value);
+        self.ranges.get(i).is_some_and(|r| r.start <= value)
+    }
+
+    /// Returns the range of the set that contains `value`.
+    pub fn range_containing(&self, value: T) -> Option<&Range<T>> {
+        let i = self.ranges.partition_point(|r| r.end <= value);
+        self.ranges.get(i).filter(|r| r.start <= value)
+    }
+
+    /// Returns `true` if any value of `range` is in the set.
+    pub fn intersects(&self, range: Range<T>) -> bool {
+        let i = self.ranges.partition_point(|r| r.end <= range.start);
+        self.ranges
+            .get(i)
+            .is_some_and(|r| r.start < range.end && range.start < range.end)
+    }
+
+    /// Adds all the values of `range` to the set, merging it with overlapping or adjacent ranges.
+    pub fn insert(&mut self, range: Range<T>) {
+        if range.start >= range.end {
+            return;
+        }
+
+        // All the ranges in `from..to` touch the new range and get merged into it.
+        let from = self.first_ending_from(range.start);
+        let to = from + self.ranges[from..].partition_point(|r| r.start <= range.end);
+
+        if from == to {
+            self.ranges.insert(from, range);
+        } else {
+            let start = range.start.min(self.ranges[from].start);
+            let end = range.end.max(self.ranges[to - 1].end);
+            self.ranges.drain(from + 1..to);
+            self.ranges[from] = start..end;
+        }
+    }
+
+    /// Removes all the values of `range` from the set, splitting ranges if needed.
+    pub fn remove(&mut self, range: Range<T>) {
+        if range.start >= range.end {
+            return;
+        }
+
+        // All the ranges in `from..to` overlap the removed range.
+        let from = self.ranges.partition_point(|r| r.end <= range.start);
+        let to = from + self.ranges[from..].partition_point(|r| r.start < range.end);
+        if from == to {
+            return;
+        }
+
+        let first = self.ranges[from].clone();
+        let last = self.ranges[to - 1].clone();
+
+        let mut leftovers = Vec::with_capacity(2);
+        if first.start < range.start {
+            leftovers.push(first.start..range.start);
+        }
+        if range.end < last.end {
+            leftovers.push(range.end..last.end);
+        }
+
+        self.ranges.splice(from..to, leftovers);
+    }
+
+    /// Adds all the ranges of `other` to this set.
+    pub fn merge(&mut self, other: &Self) {
+        for range in other.iter() {
+            self.insert(range.clone());
+        }
+    }
+
+    /// Returns a new set with the values present in either set.
+    pub fn union(&self, other: &Self) -> Self {
+        let mut result = self.clone();
+        result.merge(other);
+        result
+    }
+
+    /// Returns a new set with the values present in both sets.
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut ranges = Vec::new();
+        let (mut i, mut j) = (0, 0);
+
+        while let (Some(a), Some(b)) = (self.ranges.get(i), other.ranges.get(j)) {
+            let start = a.start.max(b.start);
+            let end = a.end.min(b.end);
+            if start < end {
+                ranges.push(start..end);
+            }
+
+            if a.end < b.end {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+
+        Self { ranges }
+    }
+
+    /// Returns a new set with the values of this set that aren't in `other`.
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut result = self.clone();
+        for range in other.iter() {
+            result.remove(range.clone());
+        }
+        result
+    }
+
+    /// Splits the set into the values below `at` and the values from `at` onwards.
+    pub fn split_at(&self, at: T) -> (Self, Self) {
+        let mut below = Vec::new();
+        let mut above = Vec::new();
+
+        for range in self.iter() {
+            if range.end <= at {
+                below.push(range.clone());
+            } else if range.start >= at {
+                above.push(range.clone());
+            } else {
+                below.push(range.start..at);
+                above.push(at..range.end);
+            }
+        }
+
+        (Self { ranges: below }, Self { ranges: above })
+    }
+
+    /// Finds the leftmost range that can hold `len` values and returns its first `len` values.
+    pub fn first_fit(&self, len: T) -> Option<Range<T>> {
+        self.ranges
+            .iter()
+            .find(|r| r.end - r.start >= len)
+            .map(|r| r.start..r.start + len)
+    }
+}
+
+impl<T: Integer> FromIterator<Range<T>> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = Range<T>>>(iter: I) -> Self {
+        let mut set = IntervalSet::new();
+        set.extend(iter);
+        set
+    }
+}
+
+impl<T: Integer> Extend<Range<T>> for IntervalSet<T> {
+    fn extend<I: IntoIterator<Item = Range<T>>>(&mut self, iter: I) {
+        iter.into_iter().for_each(|r| self.insert(r));
+    }
+}
+
+impl<T> IntoIterator for IntervalSet<T> {
+    type Item = Range<T>;
+    type IntoIter = std::vec::IntoIter<Range<T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.ranges.into_iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a IntervalSet<T> {
+    type Item = &'a Range<T>;
+    type IntoIter = std::slice::Iter<'a, Range<T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.ranges.iter()
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::single_range_in_vec_init)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn intervals_insert_working() {
+        let mut set = IntervalSet::new();
+        set.insert(10..20);
+        set.insert(0..5);
+        set.insert(30..40);
+        set.insert(7..7);
+        assert_eq!(set.ranges(), &[0..5, 10..20, 30..40]);
+
+        // adjacent ranges are merged
+        set.insert(5..8);
+        assert_eq!(set.ranges(), &[0..8, 10..20, 30..40]);
+
+        // spanning multiple ranges
+        set.insert(15..35);
+        assert_eq!(set.ranges(), &[0..8, 10..40]);
+        assert_eq!(set.total_len(), 38);
+
+        assert!(set.contains(0));
+        assert!(!set.contains(8));
+        assert!(set.contains(39));
+        assert!(!set.contains(40));
+        assert_eq!(set.range_containing(12), Some(&(10..40)));
+        assert!(set.intersects(7..12));
+        assert!(!set.intersects(8..10));
+    }
+
+    #[test]
+    fn intervals_remove_working() {
+        let mut set: IntervalSet<i32> = [0..10, 20..30].into_iter().collect();
+        set.remove(3..5);
+        assert_eq!(set.ranges(), &[0..3, 5..10, 20..30]);
+
+        set.remove(8..25);
+        assert_eq!(set.ranges(), &[0..3, 5..8, 25..30]);
+
+        set.remove(-10..4);
+        assert_eq!(set.ranges(), &[5..8, 25..30]);
+
+        set.remove(10..20);
+        assert_eq!(set.ranges(), &[5..8, 25..30]);
+
+        set.remove(0..100);
+        assert!(set.is_empty());
+    }
+
+    #[test]
+    fn intervals_set_ops_working() {
+        let a: IntervalSet<u64> = [0..10, 20..30].into_iter().collect();
+        let b: IntervalSet<u64> = [5..25, 28..40].into_iter().collect();
+
+        assert_eq!(a.union(&b).ranges(), &[0..40]);
+        assert_eq!(a.intersection(&b).ranges(), &[5..10, 20..25, 28..30]);
+        assert_eq!(a.difference(&b).ranges(), &[0..5, 25..28]);
+
+        let (below, above) = a.split_at(25);
+        assert_eq!(below.ranges(), &[0..10, 20..25]);
+        assert_eq!(above.ranges(), &[25..30]);
+
+        let mut merged = a.clone();
+        merged.merge(&b);
+        assert_eq!(merged, a.union(&b));
+    }
+
+    #[test]
+    fn intervals_first_fit_working() {
+        let set: IntervalSet<usize> = [2..4, 6..12, 15..30].into_iter().collect();
+        assert_eq!(set.first_fit(1), Some(2..3));
+        assert_eq!(set.first_fit(3), Some(6..9));
+        assert_eq!(set.first_fit(10), Some(15..25));
+        assert_eq!(set.first_fit(20), None);
+    }
+}
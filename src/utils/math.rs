@@ -0,0 +1,407 @@
+//! Number theory helpers: gcd/lcm, modular arithmetic, the chinese remainder theorem and
+//! decimal digit manipulation. Everything is generic over the primitive integer types through
+//! the [`Integer`] trait.
+//!
+//! ```
+//!   # use advent_of_code::utils::math::*;
+//!
+//!   assert_eq!(lcm_all([4u64, 6, 10]), Some(60));
+//!   assert_eq!(mod_pow(3i64, 200, 1_000_000_007), 136_318_165);
+//!   assert_eq!(crt([(2i128, 3), (3, 5), (2, 7)]), Some((23, 105)));
+//!   assert_eq!(concat_digits(12u64, 345), Some(12345));
+//! ```
+use std::fmt::Debug;
+use std::ops::{Add, Div, Mul, Neg, Rem, Sub};
+
+/// Common operations of the primitive integer types used by the functions in this module.
+pub trait Integer:
+    Copy
+    + Debug
+    + Ord
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Rem<Output = Self>
+{
+    const ZERO: Self;
+    const ONE: Self;
+    const TEN: Self;
+
+    /// Absolute value, the identity for unsigned types.
+    fn abs(self) -> Self;
+    fn rem_euclid(self, rhs: Self) -> Self;
+    fn ilog10(self) -> u32;
+    /// Base 10 logarithm of the absolute value, defined for the minimum of signed types too.
+    fn abs_ilog10(self) -> u32;
+    fn checked_add(self, rhs: Self) -> Option<Self>;
+    /// Absolute value, [`None`] for the minimum of signed types.
+    fn checked_abs(self) -> Option<Self>;
+    fn checked_rem(self, rhs: Self) -> Option<Self>;
+    fn checked_mul(self, rhs: Self) -> Option<Self>;
+    fn checked_pow(self, exp: u32) -> Option<Self>;
+
+    /// `(self + rhs) mod m` for `self` and `rhs` in `0..m`, without overflowing.
+    #[inline]
+    fn add_mod(self, rhs: Self, m: Self) -> Self {
+        if self >= m - rhs {
+            self - (m - rhs)
+        } else {
+            self + rhs
+        }
+    }
+
+    /// `(self - rhs) mod m` for `self` and `rhs` in `0..m`, without overflowing.
+    #[inline]
+    fn sub_mod(self, rhs: Self, m: Self) -> Self {
+        if self >= rhs {
+            self - rhs
+        } else {
+            self + (m - rhs)
+        }
+    }
+
+    /// `(self * rhs) mod m`. Falls back to double-and-add when the product would overflow.
+    fn mul_mod(self, rhs: Self, m: Self) -> Self {
+        let a = self.rem_euclid(m);
+        let mut b = rhs.rem_euclid(m);
+        if let Some(product) = a.checked_mul(b) {
+            return product % m;
+        }
+
+        let two = Self::ONE + Self::ONE;
+        let mut result = Self::ZERO;
+        let mut acc = a;
+        while b > Self::ZERO {
+            if b % two == Self::ONE {
+                result = result.add_mod(acc, m);
+            }
+            acc = acc.add_mod(acc, m);
+            b = b / two;
+        }
+        result
+    }
+}
+
+macro_rules! impl_integer {
+    (@common) => {
+        const ZERO: Self = 0;
+        const ONE: Self = 1;
+        const TEN: Self = 10;
+
+        #[inline]
+        fn rem_euclid(self, rhs: Self) -> Self {
+            Self::rem_euclid(self, rhs)
+        }
+
+        #[inline]
+        fn ilog10(self) -> u32 {
+            Self::ilog10(self)
+        }
+
+        #[inline]
+        fn checked_add(self, rhs: Self) -> Option<Self> {
+            Self::checked_add(self, rhs)
+        }
+
+        #[inline]
+        fn checked_mul(self, rhs: Self) -> Option<Self> {
+            Self::checked_mul(self, rhs)
+        }
+
+        #[inline]
+        fn checked_rem(self, rhs: Self) -> Option<Self> {
+            Self::checked_rem(self, rhs)
+        }
+
+        #[inline]
+        fn checked_pow(self, exp: u32) -> Option<Self> {
+            Self::checked_pow(self, exp)
+        }
+    };
+    (signed: $($t:ty),*) => {
+        $(impl Integer for $t {
+            impl_integer!(@common);
+
+            #[inline]
+            fn abs(self) -> Self {
+                Self::abs(self)
+            }
+
+            #[inline]
+            fn abs_ilog10(self) -> u32 {
+                self.unsigned_abs().ilog10()
+            }
+
+            #[inline]
+            fn checked_abs(self) -> Option<Self> {
+                Self::checked_abs(self)
+            }
+        })*
+    };
+    (unsigned: $($t:ty),*) => {
+        $(impl Integer for $t {
+            impl_integer!(@common);
+
+            #[inline]
+            fn abs(self) -> Self {
+                self
+            }
+
+            #[inline]
+            fn abs_ilog10(self) -> u32 {
+                Self::ilog10(self)
+            }
+
+            #[inline]
+            fn checked_abs(self) -> Option<Self> {
+                Some(self)
+            }
+        })*
+    };
+}
+
+impl_integer!(signed: i32, i64, i128, isize);
+impl_integer!(unsigned: u32, u64, u128, usize);
+
+/* -------------------------------------------------------------------------- */
+
+/// Greatest common divisor, always non-negative. `gcd(0, 0)` is `0`.
+///
+/// # Panics
+/// If the gcd doesn't fit into `T`, which only happens for `gcd(T::MIN, 0)` and `gcd(T::MIN, T::MIN)`
+/// of signed types.
+pub fn gcd<T: Integer>(a: T, b: T) -> T {
+    // NOTE: works on the signed values, the remainders are smaller than `b` in magnitude.
+    let (mut a, mut b) = (a, b);
+    while b != T::ZERO {
+        // only `T::MIN % -1` overflows, its remainder is zero.
+        (a, b) = (b, a.checked_rem(b).unwrap_or(T::ZERO));
+    }
+    a.checked_abs()
+        .expect("gcd overflows, it is `T::MIN` in magnitude")
+}
+
+/// Least common multiple, always non-negative. Returns `0` if either value is `0`,
+/// and [`None`] if the lcm overflows.
+pub fn lcm<T: Integer>(a: T, b: T) -> Option<T> {
+    if a == T::ZERO || b == T::ZERO {
+        return Some(T::ZERO);
+    }
+    if a == b {
+        return a.checked_abs();
+    }
+    (a / gcd(a, b)).checked_mul(b)?.checked_abs()
+}
+
+/// Greatest common divisor of all the values, `0` for an empty iterator.
+pub fn gcd_all<T: Integer>(values: impl IntoIterator<Item = T>) -> T {
+    values.into_iter().fold(T::ZERO, gcd)
+}
+
+/// Least common multiple of all the values, `1` for an empty iterator. Returns [`None`] if the lcm overflows.
+pub fn lcm_all<T: Integer>(values: impl IntoIterator<Item = T>) -> Option<T> {
+    values.into_iter().try_fold(T::ONE, lcm)
+}
+
+/// Extended euclidean algorithm. Returns `(g, x, y)` such that `a * x + b * y == g == gcd(a, b)`.
+pub fn ext_gcd<T: Integer + Neg<Output = T>>(a: T, b: T) -> (T, T, T) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_x, mut x) = (T::ONE, T::ZERO);
+    let (mut old_y, mut y) = (T::ZERO, T::ONE);
+
+    while r != T::ZERO {
+        let q = old_r / r;
+        (old_r, r) = (r, old_r - q * r);
+        (old_x, x) = (x, old_x - q * x);
+        (old_y, y) = (y, old_y - q * y);
+    }
+
+    if old_r < T::ZERO {
+        (-old_r, -old_x, -old_y)
+    } else {
+        (old_r, old_x, old_y)
+    }
+}
+
+/// Computes `base ^ exp mod m` by repeated squaring. The result is in `0..m`.
+pub fn mod_pow<T: Integer>(base: T, mut exp: u64, m: T) -> T {
+    if m == T::ONE {
+        return T::ZERO;
+    }
+
+    let mut result = T::ONE;
+    let mut base = base.rem_euclid(m);
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = result.mul_mod(base, m);
+        }
+        base = base.mul_mod(base, m);
+        exp >>= 1;
+    }
+    result
+}
+
+/// Modular multiplicative inverse of `a` modulo `m`, i.e. `x` in `0..m` such that `a * x ≡ 1 (mod m)`.
+/// Returns [`None`] if `a` and `m` aren't coprime.
+pub fn mod_inv<T: Integer>(a: T, m: T) -> Option<T> {
+    // Keep the bezout coefficient reduced modulo `m` so this works for unsigned types as well.
+    let (mut old_r, mut r) = (a.rem_euclid(m), m);
+    let (mut old_s, mut s) = (T::ONE.rem_euclid(m), T::ZERO);
+
+    while r != T::ZERO {
+        let q = old_r / r;
+        (old_r, r) = (r, old_r - q * r);
+        (old_s, s) = (s, old_s.sub_mod(q.mul_mod(s, m), m));
+    }
+
+    (old_r == T::ONE).then_some(old_s)
+}
+
+/// Solves a system of congruences `x ≡ r (mod m)` given as `(r, m)` pairs. The moduli don't have to
+/// be coprime. Returns the smallest non-negative solution together with the combined modulus
+/// (the lcm of all the moduli), or [`None`] if the system has no solution or the lcm overflows.
+pub fn crt<T: Integer>(congruences: impl IntoIterator<Item = (T, T)>) -> Option<(T, T)> {
+    congruences
+        .into_iter()
+        .try_fold((T::ZERO, T::ONE), |(r1, m1), (r2, m2)| {
+            let r2 = r2.rem_euclid(m2);
+            let g = gcd(m1, m2);
+            if r1 % g != r2 % g {
+                return None;
+            }
+
+            // x = r1 + m1 * k, where k ≡ (r2 - r1) / g * (m1 / g)^-1 (mod m2 / g)
+            let m2_g = m2 / g;
+            let diff = r2.sub_mod(r1.rem_euclid(m2), m2) / g;
+            let k = diff.mul_mod(mod_inv(m1 / g, m2_g)?, m2_g);
+            let modulus = (m1 / g).checked_mul(m2)?;
+            let x = m1.checked_mul(k)?.checked_add(r1)?;
+
+            Some((x, modulus))
+        })
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Number of decimal digits of `n`, ignoring the sign. Zero has one digit.
+#[inline]
+pub fn digit_count<T: Integer>(n: T) -> u32 {
+    if n == T::ZERO {
+        1
+    } else {
+        n.abs_ilog10() + 1
+    }
+}
+
+/// Splits off the lowest `at` decimal digits of `n`: `split_digits(123456, 2) == (1234, 56)`.
+#[inline]
+pub fn split_digits<T: Integer>(n: T, at: u32) -> (T, T) {
+    match T::TEN.checked_pow(at) {
+        Some(pow) => (n / pow, n % pow),
+        None => (T::ZERO, n),
+    }
+}
+
+/// Concatenates the decimal digits of `a` and `b`: `concat_digits(12, 345) == Some(12345)`.
+/// Returns [`None`] on overflow.
+#[inline]
+pub fn concat_digits<T: Integer>(a: T, b: T) -> Option<T> {
+    T::TEN
+        .checked_pow(digit_count(b))?
+        .checked_mul(a)?
+        .checked_add(b)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gcd_lcm_working() {
+        assert_eq!(gcd(12u64, 18), 6);
+        assert_eq!(gcd(-12i64, 18), 6);
+        assert_eq!(gcd(0i128, 7), 7);
+        assert_eq!(lcm(4u64, 6), Some(12));
+        assert_eq!(lcm(-4i64, 6), Some(12));
+        assert_eq!(lcm(0u64, 6), Some(0));
+        assert_eq!(gcd_all([12u64, 18, 27]), 3);
+        assert_eq!(lcm_all([2u64, 3, 4, 5]), Some(60));
+        assert_eq!(lcm_all(Vec::<u64>::new()), Some(1));
+    }
+
+    #[test]
+    fn gcd_lcm_handle_extremes() {
+        assert_eq!(gcd(i64::MIN, 6), 2);
+        assert_eq!(gcd(6, i64::MIN), 2);
+        assert_eq!(gcd(i64::MIN, -1), 1);
+        assert_eq!(gcd(i64::MIN, i64::MIN / 2), 1 << 62);
+        assert_eq!(gcd(u64::MAX, u64::MAX), u64::MAX);
+
+        assert_eq!(lcm(u64::MAX, 2), None);
+        assert_eq!(lcm(i64::MAX, i64::MAX - 1), None);
+        assert_eq!(lcm(i64::MIN, 1), None);
+        assert_eq!(lcm(i64::MIN, i64::MIN), None);
+        assert_eq!(lcm(i64::MIN / 2, 2), Some(1 << 62));
+        assert_eq!(lcm_all([u64::MAX, 2, 3]), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "gcd overflows")]
+    fn gcd_panics_if_unrepresentable() {
+        gcd(i64::MIN, 0);
+    }
+
+    #[test]
+    fn ext_gcd_working() {
+        for (a, b) in [(240i64, 46), (46, 240), (-240, 46), (17, 0), (0, 5)] {
+            let (g, x, y) = ext_gcd(a, b);
+            assert_eq!(g, gcd(a, b));
+            assert_eq!(a * x + b * y, g);
+        }
+    }
+
+    #[test]
+    fn mod_pow_working() {
+        assert_eq!(mod_pow(2u64, 10, 1000), 24);
+        assert_eq!(mod_pow(-2i64, 3, 5), 2);
+        assert_eq!(mod_pow(7u64, 0, 13), 1);
+        assert_eq!(mod_pow(7u64, 5, 1), 0);
+        // Operands close to the modulus don't fit into 128 bits when multiplied.
+        let m = i128::MAX - 2;
+        assert_eq!(mod_pow(m - 1, 2, m), 1);
+    }
+
+    #[test]
+    fn mod_inv_working() {
+        assert_eq!(mod_inv(3u64, 11), Some(4));
+        assert_eq!(mod_inv(10i64, 17), Some(12));
+        assert_eq!(mod_inv(-3i128, 11), Some(7));
+        assert_eq!(mod_inv(6u64, 9), None);
+    }
+
+    #[test]
+    fn crt_working() {
+        assert_eq!(crt([(2u64, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        assert_eq!(crt([(-1i64, 4), (0, 3)]), Some((3, 12)));
+        // Non-coprime moduli.
+        assert_eq!(crt([(2i128, 6), (8, 10)]), Some((8, 30)));
+        assert_eq!(crt([(1u64, 4), (2, 6)]), None);
+        assert_eq!(crt(Vec::<(u64, u64)>::new()), Some((0, 1)));
+    }
+
+    #[test]
+    fn digits_working() {
+        assert_eq!(digit_count(0u64), 1);
+        assert_eq!(digit_count(9u64), 1);
+        assert_eq!(digit_count(10u64), 2);
+        assert_eq!(digit_count(-12345i64), 5);
+        assert_eq!(digit_count(i64::MIN), 19);
+        assert_eq!(split_digits(123456u64, 2), (1234, 56));
+        assert_eq!(split_digits(1000u64, 2), (10, 0));
+        assert_eq!(split_digits(42u64, 50), (0, 42));
+        assert_eq!(concat_digits(12u64, 345), Some(12345));
+        assert_eq!(concat_digits(12u64, 0), Some(120));
+        assert_eq!(concat_digits(u64::MAX / 10, 99), None);
+    }
+}
@@ -1,5 +1,14 @@
+pub mod counter;
+pub mod cycle;
 pub mod grid;
+pub mod intervals;
+pub mod math;
+pub mod ocr;
 pub mod point;
+pub mod union_find;
 
+pub use counter::*;
 pub use grid::*;
+pub use intervals::*;
 pub use point::*;
+pub use union_find::*;
@@ -0,0 +1,224 @@
+//! Recognition of the block letters some puzzles draw on a grid.
+//!
+//! Two fonts are known: the small font with letters of 6 rows (mostly 4 columns wide)
+//! and the large font with letters of 10 rows (6 columns wide). Letters are separated by
+//! at least one empty column, the font is chosen by the height of the drawing.
+//!
+//! ```
+//!   # use advent_of_code::utils::ocr::*;
+//!
+//!   let art = "\
+//! #..#.###.
+//! #..#..#..
+//! ####..#..
+//! #..#..#..
+//! #..#..#..
+//! #..#.###.";
+//!
+//!   assert_eq!(recognize(art), Some("HI".to_string()));
+//! ```
+
+use crate::utils::{Grid, Point};
+
+/// Height of the letters of the small font.
+pub const SMALL_HEIGHT: usize = 6;
+/// Height of the letters of the large font.
+pub const LARGE_HEIGHT: usize = 10;
+
+#[rustfmt::skip]
+const SMALL_FONT: [(char, [&str; SMALL_HEIGHT]); 18] = [
+    ('A', [".##.", "#..#", "#..#", "####", "#..#", "#..#"]),
+    ('B', ["###.", "#..#", "###.", "#..#", "#..#", "###."]),
+    ('C', [".##.", "#..#", "#...", "#...", "#..#", ".##."]),
+    ('E', ["####", "#...", "###.", "#...", "#...", "####"]),
+    ('F', ["####", "#...", "###.", "#...", "#...", "#..."]),
+    ('G', [".##.", "#..#", "#...", "#.##", "#..#", ".###"]),
+    ('H', ["#..#", "#..#", "####", "#..#", "#..#", "#..#"]),
+    ('I', ["###", ".#.", ".#.", ".#.", ".#.", "###"]),
+    ('J', ["..##", "...#", "...#", "...#", "#..#", ".##."]),
+    ('K', ["#..#", "#.#.", "##..", "#.#.", "#.#.", "#..#"]),
+    ('L', ["#...", "#...", "#...", "#...", "#...", "####"]),
+    ('O', [".##.", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('P', ["###.", "#..#", "#..#", "###.", "#...", "#..."]),
+    ('R', ["###.", "#..#", "#..#", "###.", "#.#.", "#..#"]),
+    ('S', [".###", "#...", "#...", ".##.", "...#", "###."]),
+    ('U', ["#..#", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('Y', ["#...#", "#...#", ".#.#.", "..#..", "..#..", "..#.."]),
+    ('Z', ["####", "...#", "..#.", ".#..", "#...", "####"]),
+];
+
+#[rustfmt::skip]
+const LARGE_FONT: [(char, [&str; LARGE_HEIGHT]); 15] = [
+    ('A', ["..##..", ".#..#.", "#....#", "#....#", "#....#", "######", "#....#", "#....#", "#....#", "#....#"]),
+    ('B', ["#####.", "#....#", "#....#", "#....#", "#####.", "#....#", "#....#", "#....#", "#....#", "#####."]),
+    ('C', [".####.", "#....#", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#....#", ".####."]),
+    ('E', ["######", "#.....", "#.....", "#.....", "#####.", "#.....", "#.....", "#.....", "#.....", "######"]),
+    ('F', ["######", "#.....", "#.....", "#.....", "#####.", "#.....", "#.....", "#.....", "#.....", "#....."]),
+    ('G', [".####.", "#....#", "#.....", "#.....", "#.....", "#..###", "#....#", "#....#", "#...##", ".###.#"]),
+    ('H', ["#....#", "#....#", "#....#", "#....#", "######", "#....#", "#....#", "#....#", "#....#", "#....#"]),
+    ('J', ["...###", "....#.", "....#.", "....#.", "....#.", "....#.", "....#.", "#...#.", "#...#.", ".###.."]),
+    ('K', ["#....#", "#...#.", "#..#..", "#.#...", "##....", "##....", "#.#...", "#..#..", "#...#.", "#....#"]),
+    ('L', ["#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "######"]),
+    ('N', ["#....#", "##...#", "##...#", "#.#..#", "#.#..#", "#..#.#", "#..#.#", "#...##", "#...##", "#....#"]),
+    ('P', ["#####.", "#....#", "#....#", "#....#", "#####.", "#.....", "#.....", "#.....", "#.....", "#....."]),
+    ('R', ["#####.", "#....#", "#....#", "#....#", "#####.", "#..#..", "#...#.", "#...#.", "#....#", "#....#"]),
+    ('X', ["#....#", "#....#", ".#..#.", ".#..#.", "..##..", "..##..", ".#..#.", ".#..#.", "#....#", "#....#"]),
+    ('Z', ["######", ".....#", ".....#", "....#.", "...#..", "..#...", ".#....", "#.....", "#.....", "######"]),
+];
+
+/// Whether a character of a drawing is a lit pixel. Everything but `.` and spaces counts as lit,
+/// so both `#` and `█` drawings are recognized.
+fn is_lit_char(c: char) -> bool {
+    !matches!(c, '.' | ' ')
+}
+
+/// Recognizes the letters drawn in `art`, one line per row of pixels.
+/// Returns [`None`] if the drawing contains something that isn't a known letter.
+pub fn recognize(art: &str) -> Option<String> {
+    let rows = art
+        .lines()
+        .map(|line| line.chars().map(is_lit_char).collect())
+        .collect();
+
+    recognize_pixels(rows)
+}
+
+/// Recognizes the letters drawn on `grid`, where `is_lit` tells the lit cells apart.
+pub fn recognize_grid<T>(grid: &Grid<T>, is_lit: impl Fn(&T) -> bool) -> Option<String> {
+    recognize_pixels(pixels(grid, is_lit))
+}
+
+/// Renders the cells of `grid` as a drawing of `#` and `.`, which [`recognize`] reads back.
+pub fn render<T>(grid: &Grid<T>, is_lit: impl Fn(&T) -> bool) -> String {
+    pixels(grid, is_lit)
+        .iter()
+        .map(|row| row.iter().map(|&lit| if lit { '#' } else { '.' }).collect())
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+fn pixels<T>(grid: &Grid<T>, is_lit: impl Fn(&T) -> bool) -> Vec<Vec<bool>> {
+    (0..grid.height)
+        .map(|y| {
+            (0..grid.width)
+                .map(|x| is_lit(&grid[Point::new(x, y)]))
+                .collect()
+        })
+        .collect()
+}
+
+fn recognize_pixels(mut rows: Vec<Vec<bool>>) -> Option<String> {
+    // the font is chosen by the height of the letters, empty rows around them don't count.
+    while rows.last().is_some_and(|row| !row.contains(&true)) {
+        rows.pop();
+    }
+    let first = rows.iter().position(|row| row.contains(&true))?;
+    rows.drain(..first);
+
+    let lit = |x: usize, y: usize| rows[y].get(x).copied().unwrap_or(false);
+    let is_column_lit = |x: usize| (0..rows.len()).any(|y| lit(x, y));
+    let width = rows.iter().map(Vec::len).max().unwrap_or(0);
+
+    let mut text = String::new();
+    let mut x = 0;
+
+    while x < width {
+        if !is_column_lit(x) {
+            x += 1;
+            continue;
+        }
+
+        let start = x;
+        while x < width && is_column_lit(x) {
+            x += 1;
+        }
+
+        let glyph = (0..rows.len())
+            .map(|y| {
+                (start..x)
+                    .map(|x| if lit(x, y) { '#' } else { '.' })
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>();
+
+        let letter = match rows.len() {
+            SMALL_HEIGHT => lookup(&SMALL_FONT, &glyph),
+            LARGE_HEIGHT => lookup(&LARGE_FONT, &glyph),
+            _ => None,
+        }?;
+        text.push(letter);
+    }
+
+    Some(text)
+}
+
+fn lookup<const N: usize>(font: &[(char, [&str; N])], glyph: &[String]) -> Option<char> {
+    font.iter()
+        .find(|(_, rows)| rows.iter().eq(glyph.iter()))
+        .map(|(letter, _)| *letter)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn draw<const N: usize>(font: &[(char, [&str; N])], text: &str, gap: usize) -> String {
+        (0..N)
+            .map(|y| {
+                text.chars()
+                    .map(|c| font.iter().find(|(letter, _)| *letter == c).unwrap().1[y])
+                    .collect::<Vec<_>>()
+                    .join(&".".repeat(gap))
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn recognizes_small_font() {
+        let text: String = SMALL_FONT.iter().map(|(letter, _)| letter).collect();
+        assert_eq!(recognize(&draw(&SMALL_FONT, &text, 1)), Some(text));
+    }
+
+    #[test]
+    fn recognizes_large_font() {
+        let text: String = LARGE_FONT.iter().map(|(letter, _)| letter).collect();
+        // letters of the large font are separated by two columns.
+        assert_eq!(recognize(&draw(&LARGE_FONT, &text, 2)), Some(text));
+    }
+
+    #[test]
+    fn ignores_surrounding_space() {
+        let art = format!(
+            "\n..........\n{}\n..........",
+            draw(&SMALL_FONT, "EF", 1)
+                .lines()
+                .map(|line| format!("..{line}...."))
+                .collect::<Vec<_>>()
+                .join("\n")
+        );
+        assert_eq!(recognize(&art), Some("EF".into()));
+
+        let block = draw(&SMALL_FONT, "HI", 1)
+            .replace('#', "█")
+            .replace('.', " ");
+        assert_eq!(recognize(&block), Some("HI".into()));
+    }
+
+    #[test]
+    fn rejects_unknown_drawings() {
+        assert_eq!(recognize(""), None);
+        assert_eq!(recognize("#..#\n####"), None);
+
+        let mut art = draw(&SMALL_FONT, "AB", 1);
+        art.replace_range(0..1, "#");
+        assert_eq!(recognize(&art), None);
+    }
+
+    #[test]
+    fn recognizes_grids() {
+        let grid = Grid::parse(&draw(&SMALL_FONT, "ZU", 1));
+        assert_eq!(recognize_grid(&grid, |&c| c == b'#'), Some("ZU".into()));
+        assert_eq!(render(&grid, |&c| c == b'#'), draw(&SMALL_FONT, "ZU", 1));
+    }
+}
@@ -0,0 +1,312 @@
+//! A disjoint-set (union-find) structure with path compression and union by size.
+//!
+//! [`UnionFind`] works on the indices `0..n`, [`KeyedUnionFind`] accepts any hashable node
+//! (a [`Point`](crate::utils::Point), a name, ...) and maps it to an index internally:
+//!
+//! ```
+//!   # use advent_of_code::utils::*;
+//!
+//!   let mut uf = KeyedUnionFind::new();
+//!   uf.union("a", "b");
+//!   uf.union("c", "d");
+//!   uf.insert("e");
+//!   assert_eq!(uf.component_count(), 3);
+//!
+//!   uf.union("b", "c");
+//!   assert!(uf.connected(&"a", &"d"));
+//!   assert_eq!(uf.size_of(&"a"), Some(4));
+//! ```
+use std::collections::HashMap;
+use std::hash::Hash;
+
+// UNION FIND
+#[derive(Debug, Clone, Default)]
+pub struct UnionFind {
+    parent: Vec<usize>,
+    size: Vec<usize>,
+    components: usize,
+}
+
+impl UnionFind {
+    /// Creates `n` singleton components, one for each index in `0..n`.
+    pub fn new(n: usize) -> Self {
+        Self {
+            parent: (0..n).collect(),
+            size: vec![1; n],
+            components: n,
+        }
+    }
+
+    /// Adds a new singleton component and returns its index.
+    pub fn add(&mut self) -> usize {
+        let index = self.parent.len();
+        self.parent.push(index);
+        self.size.push(1);
+        self.components += 1;
+        index
+    }
+
+    /// Number of elements.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.parent.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.parent.is_empty()
+    }
+
+    /// Number of disjoint components.
+    #[inline]
+    pub fn component_count(&self) -> usize {
+        self.components
+    }
+
+    /// Returns the representative of the component containing `x`.
+    pub fn find(&mut self, mut x: usize) -> usize {
+        // Path halving: point every other node on the path to its grandparent.
+        while self.parent[x] != x {
+            self.parent[x] = self.parent[self.parent[x]];
+            x = self.parent[x];
+        }
+        x
+    }
+
+    /// Same as [`UnionFind::find`] but without compressing the path, so it works on a shared reference.
+    pub fn find_immutable(&self, mut x: usize) -> usize {
+        while self.parent[x] != x {
+            x = self.parent[x];
+        }
+        x
+    }
+
+    /// Merges the components of `a` and `b`. Returns `false` if they were already connected.
+    pub fn union(&mut self, a: usize, b: usize) -> bool {
+        let (mut a, mut b) = (self.find(a), self.find(b));
+        if a == b {
+            return false;
+        }
+
+        if self.size[a] < self.size[b] {
+            std::mem::swap(&mut a, &mut b);
+        }
+
+        self.parent[b] = a;
+        self.size[a] += self.size[b];
+        self.components -= 1;
+        true
+    }
+
+    #[inline]
+    pub fn connected(&mut self, a: usize, b: usize) -> bool {
+        self.find(a) == self.find(b)
+    }
+
+    /// Size of the component containing `x`.
+    #[inline]
+    pub fn size_of(&mut self, x: usize) -> usize {
+        let root = self.find(x);
+        self.size[root]
+    }
+
+    /// Sizes of all the components, in arbitrary order.
+    pub fn component_sizes(&self) -> Vec<usize> {
+        (0..self.len())
+            .filter(|&i| self.parent[i] == i)
+            .map(|i| self.size[i])
+            .collect()
+    }
+
+    /// Returns the members of every component. Components are ordered by their smallest member
+    /// and members are in ascending order.
+    pub fn groups(&mut self) -> Vec<Vec<usize>> {
+        let mut group_of_root = HashMap::with_capacity(self.components);
+        let mut groups: Vec<Vec<usize>> = Vec::with_capacity(self.components);
+
+        for x in 0..self.len() {
+            let root = self.find(x);
+            let group = *group_of_root.entry(root).or_insert_with(|| {
+                groups.push(Vec::with_capacity(self.size[root]));
+                groups.len() - 1
+            });
+            groups[group].push(x);
+        }
+
+        groups
+    }
+}
+
+// KEYED UNION FIND
+/// A [`UnionFind`] over arbitrary hashable nodes. Nodes are added on first use.
+#[derive(Debug, Clone)]
+pub struct KeyedUnionFind<K> {
+    inner: UnionFind,
+    indices: HashMap<K, usize>,
+    keys: Vec<K>,
+}
+
+impl<K> Default for KeyedUnionFind<K> {
+    fn default() -> Self {
+        Self {
+            inner: UnionFind::default(),
+            indices: HashMap::new(),
+            keys: Vec::new(),
+        }
+    }
+}
+
+impl<K: Hash + Eq + Clone> KeyedUnionFind<K> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds `key` as a singleton component if it isn't present yet and returns its internal index.
+    pub fn insert(&mut self, key: K) -> usize {
+        if let Some(&index) = self.indices.get(&key) {
+            return index;
+        }
+
+        let index = self.inner.add();
+        self.indices.insert(key.clone(), index);
+        self.keys.push(key);
+        index
+    }
+
+    /// The internal index of `key`, if present.
+    #[inline]
+    pub fn index_of(&self, key: &K) -> Option<usize> {
+        self.indices.get(key).copied()
+    }
+
+    /// The key with the internal index `index`.
+    #[inline]
+    pub fn key(&self, index: usize) -> &K {
+        &self.keys[index]
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    #[inline]
+    pub fn component_count(&self) -> usize {
+        self.inner.component_count()
+    }
+
+    /// Returns the representative of the component containing `key`.
+    pub fn find(&mut self, key: &K) -> Option<&K> {
+        let index = self.index_of(key)?;
+        let root = self.inner.find(index);
+        Some(&self.keys[root])
+    }
+
+    /// Merges the components of `a` and `b`, adding them first if needed.
+    /// Returns `false` if they were already connected.
+    pub fn union(&mut self, a: K, b: K) -> bool {
+        let a = self.insert(a);
+        let b = self.insert(b);
+        self.inner.union(a, b)
+    }
+
+    /// Returns `true` if both keys are present and in the same component.
+    pub fn connected(&mut self, a: &K, b: &K) -> bool {
+        match (self.index_of(a), self.index_of(b)) {
+            (Some(a), Some(b)) => self.inner.connected(a, b),
+            _ => false,
+        }
+    }
+
+    /// Size of the component containing `key`, if present.
+    pub fn size_of(&mut self, key: &K) -> Option<usize> {
+        let index = self.index_of(key)?;
+        Some(self.inner.size_of(index))
+    }
+
+    pub fn component_sizes(&self) -> Vec<usize> {
+        self.inner.component_sizes()
+    }
+
+    /// Returns the members of every component, in insertion order.
+    pub fn groups(&mut self) -> Vec<Vec<&K>> {
+        self.inner
+            .groups()
+            .into_iter()
+            .map(|group| group.into_iter().map(|i| &self.keys[i]).collect())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::Point;
+
+    #[test]
+    fn union_find_working() {
+        let mut uf = UnionFind::new(6);
+        assert_eq!(uf.component_count(), 6);
+
+        assert!(uf.union(0, 1));
+        assert!(uf.union(2, 3));
+        assert!(uf.union(1, 3));
+        assert!(!uf.union(0, 2));
+        assert_eq!(uf.component_count(), 3);
+
+        assert!(uf.connected(0, 3));
+        assert!(!uf.connected(0, 4));
+        assert_eq!(uf.size_of(2), 4);
+        assert_eq!(uf.size_of(5), 1);
+        assert_eq!(uf.find(3), uf.find_immutable(0));
+
+        let mut sizes = uf.component_sizes();
+        sizes.sort_unstable();
+        assert_eq!(sizes, vec![1, 1, 4]);
+        assert_eq!(uf.groups(), vec![vec![0, 1, 2, 3], vec![4], vec![5]]);
+
+        let new = uf.add();
+        assert_eq!(new, 6);
+        assert_eq!(uf.component_count(), 4);
+    }
+
+    #[test]
+    fn keyed_union_find_working() {
+        let mut uf = KeyedUnionFind::new();
+        let points = [
+            Point::new(0, 0),
+            Point::new(0, 1),
+            Point::new(5, 5),
+            Point::new(5, 6),
+        ];
+        points.iter().for_each(|&p| {
+            uf.insert(p);
+        });
+
+        uf.union(points[0], points[1]);
+        uf.union(points[2], points[3]);
+        assert_eq!(uf.component_count(), 2);
+        assert!(uf.connected(&points[0], &points[1]));
+        assert!(!uf.connected(&points[1], &points[2]));
+        assert!(!uf.connected(&points[1], &Point::new(9, 9)));
+        assert_eq!(uf.size_of(&points[3]), Some(2));
+        assert_eq!(uf.size_of(&Point::new(9, 9)), None);
+
+        // unknown keys are added on union
+        uf.union(Point::new(5, 7), points[3]);
+        assert_eq!(uf.len(), 5);
+        assert_eq!(uf.size_of(&points[2]), Some(3));
+        assert_eq!(
+            uf.groups(),
+            vec![
+                vec![&points[0], &points[1]],
+                vec![&points[2], &points[3], &Point::new(5, 7)]
+            ]
+        );
+    }
+}